@@ -1,31 +1,55 @@
 use seamonkey::*;
 
-use input::Action;
+use input::{ Action, ActionInfo };
+use managers::Macro;
 use themes::{ ItemTheme, TextfieldTheme };
 use elements::ComboItem;
 
 #[derive(Clone)]
 pub struct ActionItem {
     action: Action,
+    macro_name: Option<SharedString>,
     name: SharedString,
+    text: SharedString,
 }
 
 impl ActionItem {
 
-    pub fn new(action: Action, name: &'static str) -> Self {
+    pub fn new(info: &ActionInfo, bindings: SharedString) -> Self {
+        let name = SharedString::from(info.name);
+
+        let text = match bindings.is_empty() {
+            true => format_shared!("{}   - {}", name, info.description),
+            false => format_shared!("{}   [{}]   - {}", name, bindings, info.description),
+        };
+
+        return Self {
+            action: info.action,
+            macro_name: None,
+            name: name,
+            text: text,
+        }
+    }
+
+    pub fn from_macro(entry: &Macro) -> Self {
+        let name = format_shared!("macro {}", entry.name);
+        let text = format_shared!("{}   - play macro ({} steps)", name, entry.steps.len());
+
         return Self {
-            action: action,
-            name: SharedString::from(name),
+            action: Action::PlayMacro,
+            macro_name: Some(entry.name.clone()),
+            name: name,
+            text: text,
         }
     }
 }
 
 impl ComboItem for ActionItem {
 
-    type Value = Action;
+    type Value = (Action, Option<SharedString>);
 
     fn display_name(&self) -> SharedString {
-        return self.name.clone();
+        return self.text.clone();
     }
 
     fn update_name(&self) -> SharedString {
@@ -40,6 +64,6 @@ impl ComboItem for ActionItem {
     }
 
     fn return_value(&self) -> Self::Value {
-        return self.action;
+        return (self.action, self.macro_name.clone());
     }
 }
@@ -0,0 +1,180 @@
+use seamonkey::*;
+
+use sfml::graphics::*;
+use sfml::system::Vector2f;
+use sfml::window::Key;
+
+use input::{ Action, ActionCategory, Binding, BindingScope, KeyEvent, Modifiers, MouseEvent, ACTIONS };
+use themes::DialogueTheme;
+use elements::{ ComboBox, Textfield };
+use dialogues::{ DialogueMode, DialogueStatus };
+use dialogues::action::ActionItem;
+use managers::LanguageManager;
+use interface::InterfaceContext;
+
+#[derive(Clone)]
+enum EditorState {
+    Select,
+    Capture(Action),
+    Review(Action, Binding),
+}
+
+pub struct BindingDialogue {
+    combobox: ComboBox<ActionItem>,
+    state: EditorState,
+    conflicts: Vec<SharedString>,
+    size: Vector2f,
+    position: Vector2f,
+}
+
+impl BindingDialogue {
+
+    pub fn new(language_manager: &mut LanguageManager) -> Self {
+        Self {
+            combobox: ComboBox::new(language_manager, "edit binding", 0, false, Vec::new()),
+            state: EditorState::Select,
+            conflicts: Vec::new(),
+            size: Vector2f::new(0., 0.),
+            position: Vector2f::new(0., 0.),
+        }
+    }
+
+    pub fn open(&mut self, interface_context: &InterfaceContext, language_manager: &mut LanguageManager) -> DialogueMode {
+        let scopes = [ BindingScope::Global ];
+        let items = ACTIONS.iter()
+            .filter(|info| info.category != ActionCategory::Dialogue)
+            .map(|info| ActionItem::new(info, interface_context.display_bindings(info.action, &scopes)))
+            .collect();
+
+        self.combobox.set_items(items);
+        self.combobox.clear(language_manager);
+        self.state = EditorState::Select;
+        self.conflicts.clear();
+        return DialogueMode::EditBinding;
+    }
+
+    pub fn is_capturing(&self) -> bool {
+        match self.state {
+            EditorState::Capture(..) => return true,
+            _other => return false,
+        }
+    }
+
+    pub fn capture(&mut self, interface_context: &InterfaceContext, key_event: KeyEvent) {
+        let action = match self.state {
+            EditorState::Capture(action) => action,
+            _other => return,
+        };
+
+        if key_event.trigger == Key::ESCAPE && key_event.modifiers == Modifiers::new() {
+            self.state = EditorState::Select;
+            return;
+        }
+
+        let binding = Binding::new(key_event.trigger, key_event.modifiers, Modifiers::new());
+        let modified = key_event.modifiers.control || key_event.modifiers.alt || key_event.modifiers.system;
+        self.conflicts = interface_context.bindings.iter()
+            .filter(|(scope, sequence, other)| *other != action && sequence[0].overlaps(&binding) && sequence[0].is_comparable(&binding) && !(*scope == BindingScope::Normal && modified))
+            .map(|(scope, sequence, other)| match scope {
+                BindingScope::Global => format_shared!("conflicts with {} ({})", other.info().name, sequence[0].display()),
+                scope => format_shared!("conflicts with {} ({} in {})", other.info().name, sequence[0].display(), scope.name()),
+            })
+            .collect();
+
+        self.state = EditorState::Review(action, binding);
+    }
+
+    pub fn handle_action(&mut self, interface_context: &InterfaceContext, language_manager: &mut LanguageManager, action: Action) -> DialogueStatus {
+        match self.state.clone() {
+
+            EditorState::Select => {
+                let status = self.combobox.handle_action(interface_context, language_manager, action);
+
+                if status.completed {
+                    self.conflicts.clear();
+                    self.state = EditorState::Capture(self.combobox.get_value().0);
+                    return DialogueStatus::handled();
+                }
+
+                return status;
+            },
+
+            EditorState::Capture(..) => match action {
+                Action::Abort => self.state = EditorState::Select,
+                _other => { },
+            },
+
+            EditorState::Review(selected, _binding) => match action {
+                Action::Confirm => return DialogueStatus::completed(),
+                Action::Abort => self.state = EditorState::Select,
+                Action::FocusNext => {
+                    self.conflicts.clear();
+                    self.state = EditorState::Capture(selected);
+                },
+                _other => { },
+            },
+        }
+
+        return DialogueStatus::handled();
+    }
+
+    pub fn get_value(&self) -> Option<(Action, Binding)> {
+        match &self.state {
+            EditorState::Review(action, binding) => return Some((*action, binding.clone())),
+            _other => return None,
+        }
+    }
+
+    pub fn get_hovered(&self) -> Option<usize> {
+        return self.combobox.get_hovered();
+    }
+
+    pub fn handle_mouse(&mut self, language_manager: &mut LanguageManager, mouse_event: MouseEvent) -> Option<Action> {
+        match self.state {
+            EditorState::Select => return self.combobox.handle_mouse(language_manager, mouse_event),
+            _other => return None,
+        }
+    }
+
+    pub fn add_character(&mut self, language_manager: &mut LanguageManager, character: Character) {
+        if let EditorState::Select = self.state {
+            self.combobox.add_character(language_manager, character);
+        }
+    }
+
+    pub fn update_layout(&mut self, interface_context: &InterfaceContext, theme: &DialogueTheme, size: Vector2f, position: Vector2f) {
+        self.combobox.update_layout(interface_context, theme, size, position);
+        self.size = size;
+        self.position = position;
+    }
+
+    fn render_lines(&self, framebuffer: &mut RenderTexture, interface_context: &InterfaceContext, theme: &DialogueTheme, message: SharedString) {
+        let dialogue_height = theme.height * interface_context.font_size as f32;
+        let size = Vector2f::new(self.size.x, dialogue_height);
+        let mut position = self.position;
+
+        Textfield::render(framebuffer, interface_context, &theme.focused_item_theme.default_theme, &message, size, position, dialogue_height);
+
+        for conflict in self.conflicts.iter() {
+            position.y += dialogue_height + theme.unfocused_item_theme.padding * interface_context.font_size as f32;
+            Textfield::render(framebuffer, interface_context, &theme.unfocused_item_theme.special_theme, conflict, size, position, dialogue_height);
+        }
+    }
+
+    pub fn render(&self, framebuffer: &mut RenderTexture, interface_context: &InterfaceContext, theme: &DialogueTheme) {
+        match &self.state {
+
+            EditorState::Select => self.combobox.render(framebuffer, interface_context, theme, true),
+
+            EditorState::Capture(action) => {
+                let message = format_shared!("press a key combination for {} (escape to cancel)", action.info().name);
+                self.render_lines(framebuffer, interface_context, theme, message);
+            },
+
+            EditorState::Review(action, binding) => {
+                let message = format_shared!("{}: {}   (confirm to save, focus next to capture again)", action.info().name, binding.display());
+                self.render_lines(framebuffer, interface_context, theme, message);
+            },
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use seamonkey::*;
+
+use themes::{ ItemTheme, TextfieldTheme };
+use elements::ComboItem;
+
+#[derive(Clone)]
+pub struct CheatItem {
+    text: SharedString,
+    header: bool,
+}
+
+impl CheatItem {
+
+    pub fn header(text: SharedString) -> Self {
+        return Self {
+            text: text,
+            header: true,
+        }
+    }
+
+    pub fn binding(text: SharedString) -> Self {
+        return Self {
+            text: text,
+            header: false,
+        }
+    }
+}
+
+impl ComboItem for CheatItem {
+
+    type Value = ();
+
+    fn display_name(&self) -> SharedString {
+        return self.text.clone();
+    }
+
+    fn update_name(&self) -> SharedString {
+        return self.text.clone();
+    }
+
+    fn display_theme<'t>(&self, theme: &'t ItemTheme) -> &'t TextfieldTheme {
+        match self.header {
+            true => return &theme.special_theme,
+            false => return &theme.default_theme,
+        }
+    }
+
+    fn return_value(&self) -> Self::Value {
+        return ();
+    }
+}
@@ -0,0 +1,83 @@
+mod item;
+
+use seamonkey::*;
+
+use sfml::graphics::*;
+use sfml::system::Vector2f;
+
+use input::{ Action, BindingScope, MouseEvent, display_sequence };
+use themes::DialogueTheme;
+use elements::ComboBox;
+use dialogues::{ DialogueMode, DialogueStatus };
+use managers::LanguageManager;
+use interface::InterfaceContext;
+
+use self::item::CheatItem;
+
+pub struct CheatSheetDialogue {
+    combobox: ComboBox<CheatItem>,
+}
+
+impl CheatSheetDialogue {
+
+    pub fn new(language_manager: &mut LanguageManager) -> Self {
+        Self {
+            combobox: ComboBox::new(language_manager, "binding", 0, false, Vec::new()),
+        }
+    }
+
+    pub fn open(&mut self, interface_context: &InterfaceContext, language_manager: &mut LanguageManager) -> DialogueMode {
+        let mut scopes = vec![BindingScope::Global, BindingScope::Editor];
+        let mut items = Vec::new();
+
+        for (scope, _, _) in interface_context.bindings.iter() {
+            if !scopes.contains(scope) {
+                scopes.push(scope.clone());
+            }
+        }
+
+        for scope in scopes.iter() {
+            let mut bindings: Vec<_> = interface_context.bindings.iter().filter(|(other, _, _)| other == scope).collect();
+
+            if bindings.is_empty() {
+                continue;
+            }
+
+            bindings.sort_by_key(|(_, _, action)| action.info().name);
+            items.push(CheatItem::header(scope.name()));
+
+            for (_, sequence, action) in bindings.into_iter() {
+                let info = action.info();
+                items.push(CheatItem::binding(format_shared!("{}   {} - {}", display_sequence(sequence), info.name, info.description)));
+            }
+        }
+
+        self.combobox.set_items(items);
+        self.combobox.clear(language_manager);
+        return DialogueMode::CheatSheet;
+    }
+
+    pub fn handle_action(&mut self, interface_context: &InterfaceContext, language_manager: &mut LanguageManager, action: Action) -> DialogueStatus {
+        return self.combobox.handle_action(interface_context, language_manager, action);
+    }
+
+    pub fn get_hovered(&self) -> Option<usize> {
+        return self.combobox.get_hovered();
+    }
+
+    pub fn handle_mouse(&mut self, language_manager: &mut LanguageManager, mouse_event: MouseEvent) -> Option<Action> {
+        return self.combobox.handle_mouse(language_manager, mouse_event);
+    }
+
+    pub fn add_character(&mut self, language_manager: &mut LanguageManager, character: Character) {
+        self.combobox.add_character(language_manager, character);
+    }
+
+    pub fn update_layout(&mut self, interface_context: &InterfaceContext, theme: &DialogueTheme, size: Vector2f, position: Vector2f) {
+        self.combobox.update_layout(interface_context, theme, size, position);
+    }
+
+    pub fn render(&self, framebuffer: &mut RenderTexture, interface_context: &InterfaceContext, theme: &DialogueTheme) {
+        self.combobox.render(framebuffer, interface_context, theme, true);
+    }
+}
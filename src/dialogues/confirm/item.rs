@@ -0,0 +1,44 @@
+use seamonkey::*;
+
+use themes::{ ItemTheme, TextfieldTheme };
+use elements::ComboItem;
+
+#[derive(Clone)]
+pub struct ConfirmItem {
+    name: SharedString,
+    confirmed: bool,
+}
+
+impl ConfirmItem {
+
+    pub fn new(name: &'static str, confirmed: bool) -> Self {
+        return Self {
+            name: SharedString::from(name),
+            confirmed: confirmed,
+        }
+    }
+}
+
+impl ComboItem for ConfirmItem {
+
+    type Value = bool;
+
+    fn display_name(&self) -> SharedString {
+        return self.name.clone();
+    }
+
+    fn update_name(&self) -> SharedString {
+        return self.name.clone();
+    }
+
+    fn display_theme<'t>(&self, theme: &'t ItemTheme) -> &'t TextfieldTheme {
+        match self.confirmed {
+            true => return &theme.special_theme,
+            false => return &theme.default_theme,
+        }
+    }
+
+    fn return_value(&self) -> Self::Value {
+        return self.confirmed;
+    }
+}
@@ -0,0 +1,70 @@
+mod item;
+
+use seamonkey::*;
+
+use sfml::graphics::*;
+use sfml::system::Vector2f;
+
+use input::{ Action, MouseEvent };
+use themes::DialogueTheme;
+use elements::ComboBox;
+use dialogues::{ DialogueMode, DialogueStatus };
+use managers::LanguageManager;
+use interface::InterfaceContext;
+
+use self::item::ConfirmItem;
+
+pub struct ConfirmDialogue {
+    combobox: ComboBox<ConfirmItem>,
+}
+
+impl ConfirmDialogue {
+
+    pub fn new(language_manager: &mut LanguageManager, description: &'static str, confirm_name: &'static str) -> Self {
+        let items = vec![
+            ConfirmItem::new("cancel", false),
+            ConfirmItem::new(confirm_name, true),
+        ];
+
+        Self {
+            combobox: ComboBox::new(language_manager, description, 0, false, items),
+        }
+    }
+
+    pub fn open(&mut self, language_manager: &mut LanguageManager, file_name: SharedString) -> DialogueMode {
+        self.clear(language_manager);
+        return DialogueMode::CloseBuffer(file_name);
+    }
+
+    pub fn handle_action(&mut self, interface_context: &InterfaceContext, language_manager: &mut LanguageManager, action: Action) -> DialogueStatus {
+        return self.combobox.handle_action(interface_context, language_manager, action);
+    }
+
+    pub fn get_value(&self) -> bool {
+        return self.combobox.get_value();
+    }
+
+    pub fn clear(&mut self, language_manager: &mut LanguageManager) {
+        self.combobox.clear(language_manager);
+    }
+
+    pub fn get_hovered(&self) -> Option<usize> {
+        return self.combobox.get_hovered();
+    }
+
+    pub fn handle_mouse(&mut self, language_manager: &mut LanguageManager, mouse_event: MouseEvent) -> Option<Action> {
+        return self.combobox.handle_mouse(language_manager, mouse_event);
+    }
+
+    pub fn add_character(&mut self, language_manager: &mut LanguageManager, character: Character) {
+        self.combobox.add_character(language_manager, character);
+    }
+
+    pub fn update_layout(&mut self, interface_context: &InterfaceContext, theme: &DialogueTheme, size: Vector2f, position: Vector2f) {
+        self.combobox.update_layout(interface_context, theme, size, position);
+    }
+
+    pub fn render(&self, framebuffer: &mut RenderTexture, interface_context: &InterfaceContext, theme: &DialogueTheme) {
+        self.combobox.render(framebuffer, interface_context, theme, true);
+    }
+}
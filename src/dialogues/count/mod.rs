@@ -0,0 +1,88 @@
+use seamonkey::*;
+
+use sfml::graphics::*;
+use sfml::system::Vector2f;
+
+use input::{ Action, MouseEvent };
+use themes::DialogueTheme;
+use elements::TextBox;
+use dialogues::{ DialogueMode, DialogueStatus };
+use managers::LanguageManager;
+use interface::InterfaceContext;
+
+pub struct CountDialogue {
+    textbox: TextBox,
+    size: Vector2f,
+    position: Vector2f,
+    dialogue_height: f32,
+}
+
+impl CountDialogue {
+
+    pub fn new(language_manager: &mut LanguageManager) -> Self {
+        Self {
+            textbox: TextBox::new(language_manager, "repeat count", 0),
+            size: Vector2f::new(0., 0.),
+            position: Vector2f::new(0., 0.),
+            dialogue_height: 0.,
+        }
+    }
+
+    pub fn open(&mut self, language_manager: &mut LanguageManager) -> DialogueMode {
+        self.textbox.clear(language_manager);
+        return DialogueMode::Count;
+    }
+
+    pub fn get_value(&self) -> Option<usize> {
+        match self.textbox.get_text().printable().trim().parse::<usize>() {
+            Ok(0) => return None,
+            Ok(count) => return Some(count),
+            Err(_error) => return None,
+        }
+    }
+
+    pub fn handle_action(&mut self, language_manager: &mut LanguageManager, action: Action) -> DialogueStatus {
+        if let Some(action) = self.textbox.handle_action(language_manager, action) {
+            match action {
+
+                Action::Confirm => match self.get_value() {
+                    Some(_count) => return DialogueStatus::completed(),
+                    None => return DialogueStatus::handled(),
+                },
+
+                Action::Abort => return DialogueStatus::aborted(),
+
+                _unhandled => return DialogueStatus::unhandled(),
+            }
+        }
+
+        return DialogueStatus::handled();
+    }
+
+    pub fn handle_mouse(&mut self, mouse_event: MouseEvent) -> Option<Action> {
+        if let MouseEvent::Pressed(position, _click_count, _add_selection) = mouse_event {
+            let relative_position = position - self.position;
+
+            if relative_position.x < 0.0 || relative_position.x > self.size.x || relative_position.y < 0.0 || relative_position.y > self.dialogue_height {
+                return Some(Action::Abort);
+            }
+        }
+
+        return None;
+    }
+
+    pub fn add_character(&mut self, language_manager: &mut LanguageManager, character: Character) {
+        self.textbox.add_character(language_manager, character);
+    }
+
+    pub fn update_layout(&mut self, interface_context: &InterfaceContext, theme: &DialogueTheme, size: Vector2f, position: Vector2f) {
+        self.textbox.update_layout(interface_context, theme, size, position);
+        self.size = size;
+        self.position = position;
+        self.dialogue_height = theme.height * interface_context.font_size as f32;
+    }
+
+    pub fn render(&self, framebuffer: &mut RenderTexture, interface_context: &InterfaceContext, theme: &DialogueTheme) {
+        self.textbox.render(framebuffer, interface_context, theme, true);
+    }
+}
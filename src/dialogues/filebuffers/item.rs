@@ -2,17 +2,22 @@ use seamonkey::*;
 
 use themes::{ ItemTheme, TextfieldTheme };
 use elements::ComboItem;
+use filebuffer::Filebuffer;
 
 #[derive(Clone)]
 pub struct BufferItem {
     file_name: SharedString,
+    language: SharedString,
+    modified: bool,
 }
 
 impl BufferItem {
 
-    pub fn new(file_name: &str) -> Self {
+    pub fn new(file_name: &str, filebuffer: &Filebuffer) -> Self {
         return Self {
             file_name: SharedString::from(file_name),
+            language: filebuffer.get_language(),
+            modified: filebuffer.is_modified(),
         }
     }
 }
@@ -22,7 +27,10 @@ impl ComboItem for BufferItem {
     type Value = SharedString;
 
     fn display_name(&self) -> SharedString {
-        return self.file_name.clone();
+        match self.modified {
+            true => return format_shared!("{} [modified]   ({})", self.file_name, self.language),
+            false => return format_shared!("{}   ({})", self.file_name, self.language),
+        }
     }
 
     fn update_name(&self) -> SharedString {
@@ -30,7 +38,10 @@ impl ComboItem for BufferItem {
     }
 
     fn display_theme<'t>(&self, theme: &'t ItemTheme) -> &'t TextfieldTheme {
-        return &theme.default_theme;
+        match self.modified {
+            true => return &theme.special_theme,
+            false => return &theme.default_theme,
+        }
     }
 
     fn return_value(&self) -> Self::Value {
@@ -5,7 +5,7 @@ use seamonkey::*;
 use sfml::graphics::*;
 use sfml::system::Vector2f;
 
-use input::Action;
+use input::{ Action, MouseEvent };
 use themes::{ DialogueTheme, ItemTheme, TextfieldTheme };
 use elements::{ ComboBox, ComboItem };
 use dialogues::{ DialogueMode, DialogueStatus };
@@ -14,8 +14,16 @@ use managers::{ FilebufferManager, LanguageManager };
 
 use self::item::BufferItem;
 
+#[derive(Clone, Debug)]
+pub enum BufferRequest {
+    Close(SharedString),
+    Save(SharedString),
+    Reveal(SharedString),
+}
+
 pub struct FilebuffersDialogue {
     combobox: ComboBox<BufferItem>,
+    request: Option<BufferRequest>,
 }
 
 impl FilebuffersDialogue {
@@ -23,6 +31,7 @@ impl FilebuffersDialogue {
     pub fn new(language_manager: &mut LanguageManager) -> Self {
         Self {
             combobox: ComboBox::new(language_manager, "recently opened files", 0, false, Vec::new()),
+            request: None,
         }
     }
 
@@ -32,26 +41,38 @@ impl FilebuffersDialogue {
         return DialogueMode::Filebuffers;
     }
 
-    fn update_items(&mut self, filebuffer_manager: &FilebufferManager) {
-        let items = filebuffer_manager.iter().map(|(name, _)| BufferItem::new(name)).collect();
+    pub fn update_items(&mut self, filebuffer_manager: &FilebufferManager) {
+        let items = filebuffer_manager.iter().map(|(name, filebuffer)| BufferItem::new(name, filebuffer)).collect();
         self.combobox.set_items(items);
     }
 
+    pub fn take_request(&mut self) -> Option<BufferRequest> {
+        return self.request.take();
+    }
+
     pub fn handle_action(&mut self, interface_context: &InterfaceContext, filebuffer_manager: &mut FilebufferManager, language_manager: &mut LanguageManager, action: Action) -> DialogueStatus {
 
-        if let Action::Filebuffers = action {
-            return DialogueStatus::handled();
-        }
+        if !self.combobox.is_textbox_focused() {
+            let buffer_name = self.combobox.get_text();
+
+            match action {
 
-        if let Action::Delete = action {
-            if !self.combobox.is_textbox_focused() {
-                let buffer_name = self.combobox.get_text();
+                Action::Delete | Action::CloseBuffer => {
+                    self.request = Some(BufferRequest::Close(buffer_name));
+                    return DialogueStatus::handled();
+                },
 
-                // make sure that buffer has no unsaved changes
+                Action::SaveFile => {
+                    self.request = Some(BufferRequest::Save(buffer_name));
+                    return DialogueStatus::handled();
+                },
 
-                filebuffer_manager.remove(&buffer_name.serialize());
-                self.combobox.remove_selected_item(interface_context, language_manager);
-                return DialogueStatus::handled();
+                Action::Open => {
+                    self.request = Some(BufferRequest::Reveal(buffer_name));
+                    return DialogueStatus::aborted();
+                },
+
+                _other => { },
             }
         }
 
@@ -66,6 +87,14 @@ impl FilebuffersDialogue {
         self.combobox.clear(language_manager);
     }
 
+    pub fn get_hovered(&self) -> Option<usize> {
+        return self.combobox.get_hovered();
+    }
+
+    pub fn handle_mouse(&mut self, language_manager: &mut LanguageManager, mouse_event: MouseEvent) -> Option<Action> {
+        return self.combobox.handle_mouse(language_manager, mouse_event);
+    }
+
     pub fn add_character(&mut self, language_manager: &mut LanguageManager, character: Character) {
         self.combobox.add_character(language_manager, character);
     }
@@ -0,0 +1,48 @@
+use seamonkey::*;
+
+use std::cmp::Ordering;
+
+use themes::{ ItemTheme, TextfieldTheme };
+use elements::ComboItem;
+
+#[derive(Clone)]
+pub struct FindItem {
+    file_name: SharedString,
+}
+
+impl FindItem {
+
+    pub fn new(file_name: &str) -> Self {
+        return Self {
+            file_name: SharedString::from(file_name),
+        }
+    }
+}
+
+impl ComboItem for FindItem {
+
+    type Value = SharedString;
+
+    fn display_name(&self) -> SharedString {
+        return self.file_name.clone();
+    }
+
+    fn update_name(&self) -> SharedString {
+        return self.file_name.clone();
+    }
+
+    fn display_theme<'t>(&self, theme: &'t ItemTheme) -> &'t TextfieldTheme {
+        return &theme.default_theme;
+    }
+
+    fn return_value(&self) -> Self::Value {
+        return self.file_name.clone();
+    }
+
+    fn compare(&self, other: &Self) -> Ordering {
+        match self.file_name.len().cmp(&other.file_name.len()) {
+            Ordering::Equal => return self.file_name.serialize().cmp(&other.file_name.serialize()),
+            ordering => return ordering,
+        }
+    }
+}
@@ -0,0 +1,94 @@
+mod item;
+
+use seamonkey::*;
+
+use std::path::PathBuf;
+use std::sync::mpsc::{ Receiver, TryRecvError };
+
+use sfml::graphics::*;
+use sfml::system::Vector2f;
+
+use input::{ Action, MouseEvent };
+use themes::DialogueTheme;
+use elements::ComboBox;
+use dialogues::{ DialogueMode, DialogueStatus };
+use managers::LanguageManager;
+use interface::InterfaceContext;
+use system::walk_project;
+
+use self::item::FindItem;
+
+pub struct FindDialogue {
+    combobox: ComboBox<FindItem>,
+    receiver: Option<Receiver<Vec<String>>>,
+}
+
+impl FindDialogue {
+
+    pub fn new(language_manager: &mut LanguageManager) -> Self {
+        Self {
+            combobox: ComboBox::new(language_manager, "find file", 0, false, Vec::new()),
+            receiver: None,
+        }
+    }
+
+    pub fn open(&mut self, language_manager: &mut LanguageManager) -> DialogueMode {
+        self.combobox.set_items(Vec::new());
+        self.combobox.clear(language_manager);
+        self.receiver = Some(walk_project(PathBuf::from(".")));
+        return DialogueMode::Find;
+    }
+
+    pub fn close(&mut self) {
+        self.receiver = None;
+    }
+
+    pub fn poll(&mut self) -> bool {
+        let mut received = false;
+
+        while let Some(receiver) = &self.receiver {
+            match receiver.try_recv() {
+
+                Ok(batch) => {
+                    let items = batch.iter().map(|file_name| FindItem::new(file_name)).collect();
+                    self.combobox.add_items(items);
+                    received = true;
+                },
+
+                Err(TryRecvError::Empty) => break,
+
+                Err(TryRecvError::Disconnected) => self.receiver = None,
+            }
+        }
+
+        return received;
+    }
+
+    pub fn handle_action(&mut self, interface_context: &InterfaceContext, language_manager: &mut LanguageManager, action: Action) -> DialogueStatus {
+        return self.combobox.handle_action(interface_context, language_manager, action);
+    }
+
+    pub fn get_value(&self) -> SharedString {
+        return self.combobox.get_value();
+    }
+
+    pub fn get_hovered(&self) -> Option<usize> {
+        return self.combobox.get_hovered();
+    }
+
+    pub fn handle_mouse(&mut self, language_manager: &mut LanguageManager, mouse_event: MouseEvent) -> Option<Action> {
+        return self.combobox.handle_mouse(language_manager, mouse_event);
+    }
+
+    pub fn add_character(&mut self, language_manager: &mut LanguageManager, character: Character) {
+        self.combobox.add_character(language_manager, character);
+    }
+
+    pub fn update_layout(&mut self, interface_context: &InterfaceContext, theme: &DialogueTheme, size: Vector2f, position: Vector2f) {
+        self.combobox.update_layout(interface_context, theme, size, position);
+    }
+
+    pub fn render(&self, framebuffer: &mut RenderTexture, interface_context: &InterfaceContext, theme: &DialogueTheme) {
+        self.combobox.render(framebuffer, interface_context, theme, true);
+    }
+}
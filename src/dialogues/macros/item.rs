@@ -0,0 +1,43 @@
+use seamonkey::*;
+
+use themes::{ ItemTheme, TextfieldTheme };
+use elements::ComboItem;
+
+#[derive(Clone)]
+pub struct MacroItem {
+    name: SharedString,
+    text: SharedString,
+}
+
+impl MacroItem {
+
+    pub fn new(name: SharedString, step_count: usize) -> Self {
+        let text = format_shared!("{}   - {} steps", name, step_count);
+
+        return Self {
+            name: name,
+            text: text,
+        }
+    }
+}
+
+impl ComboItem for MacroItem {
+
+    type Value = SharedString;
+
+    fn display_name(&self) -> SharedString {
+        return self.text.clone();
+    }
+
+    fn update_name(&self) -> SharedString {
+        return self.name.clone();
+    }
+
+    fn display_theme<'t>(&self, theme: &'t ItemTheme) -> &'t TextfieldTheme {
+        return &theme.default_theme;
+    }
+
+    fn return_value(&self) -> Self::Value {
+        return self.name.clone();
+    }
+}
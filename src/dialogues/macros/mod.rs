@@ -0,0 +1,69 @@
+mod item;
+
+use seamonkey::*;
+
+use sfml::graphics::*;
+use sfml::system::Vector2f;
+
+use input::{ Action, MouseEvent };
+use themes::DialogueTheme;
+use elements::ComboBox;
+use dialogues::{ DialogueMode, DialogueStatus };
+use managers::{ LanguageManager, MacroManager };
+use interface::InterfaceContext;
+
+use self::item::MacroItem;
+
+pub struct MacroDialogue {
+    combobox: ComboBox<MacroItem>,
+}
+
+impl MacroDialogue {
+
+    pub fn new(language_manager: &mut LanguageManager) -> Self {
+        Self {
+            combobox: ComboBox::new(language_manager, "macro name", 0, true, Vec::new()),
+        }
+    }
+
+    pub fn open(&mut self, macro_manager: &MacroManager, language_manager: &mut LanguageManager) -> DialogueMode {
+        let items = macro_manager.iter().map(|entry| MacroItem::new(entry.name.clone(), entry.steps.len())).collect();
+        self.combobox.set_items(items);
+        self.combobox.clear(language_manager);
+        return DialogueMode::NameMacro;
+    }
+
+    pub fn handle_action(&mut self, interface_context: &InterfaceContext, language_manager: &mut LanguageManager, action: Action) -> DialogueStatus {
+        let status = self.combobox.handle_action(interface_context, language_manager, action);
+
+        if status.completed && self.combobox.get_text().is_empty() {
+            return DialogueStatus::handled();
+        }
+
+        return status;
+    }
+
+    pub fn get_text(&self) -> SharedString {
+        return self.combobox.get_text();
+    }
+
+    pub fn get_hovered(&self) -> Option<usize> {
+        return self.combobox.get_hovered();
+    }
+
+    pub fn handle_mouse(&mut self, language_manager: &mut LanguageManager, mouse_event: MouseEvent) -> Option<Action> {
+        return self.combobox.handle_mouse(language_manager, mouse_event);
+    }
+
+    pub fn add_character(&mut self, language_manager: &mut LanguageManager, character: Character) {
+        self.combobox.add_character(language_manager, character);
+    }
+
+    pub fn update_layout(&mut self, interface_context: &InterfaceContext, theme: &DialogueTheme, size: Vector2f, position: Vector2f) {
+        self.combobox.update_layout(interface_context, theme, size, position);
+    }
+
+    pub fn render(&self, framebuffer: &mut RenderTexture, interface_context: &InterfaceContext, theme: &DialogueTheme) {
+        self.combobox.render(framebuffer, interface_context, theme, true);
+    }
+}
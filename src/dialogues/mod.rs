@@ -7,13 +7,29 @@ mod theme;
 mod replace;
 mod action;
 mod notes;
+mod recent;
+mod confirm;
+mod find;
+mod report;
+mod cheatsheet;
+mod binding;
+mod macros;
+mod count;
 
 pub use self::mode::DialogueMode;
 pub use self::status::DialogueStatus;
 pub use self::open::OpenDialogue;
-pub use self::filebuffers::FilebuffersDialogue;
+pub use self::filebuffers::{ FilebuffersDialogue, BufferRequest };
 pub use self::language::LanguageDialogue;
 pub use self::theme::ThemeDialogue;
 pub use self::replace::ReplaceDialogue;
 pub use self::action::ActionDialogue;
 pub use self::notes::NotesDialogue;
+pub use self::recent::RecentDialogue;
+pub use self::confirm::ConfirmDialogue;
+pub use self::find::FindDialogue;
+pub use self::report::BindingReportDialogue;
+pub use self::cheatsheet::CheatSheetDialogue;
+pub use self::binding::BindingDialogue;
+pub use self::macros::MacroDialogue;
+pub use self::count::CountDialogue;
@@ -11,4 +11,12 @@ pub enum DialogueMode {
     Theme,
     Replace(Vec<Selection>),
     Action,
+    Recent,
+    Find,
+    BindingReport,
+    CheatSheet,
+    EditBinding,
+    NameMacro,
+    Count,
+    CloseBuffer(SharedString),
 }
@@ -2,6 +2,7 @@ use seamonkey::*;
 
 use themes::{ ItemTheme, TextfieldTheme };
 use elements::ComboItem;
+use filebuffer::BracketNote;
 
 #[derive(Clone)]
 pub struct NoteItem {
@@ -17,6 +18,13 @@ impl NoteItem {
             index: note.position.index,
         }
     }
+
+    pub fn from_bracket(note: &BracketNote) -> Self {
+        return Self {
+            text: format_shared!("line {}: bracket - {}", note.line, note.message),
+            index: note.index,
+        }
+    }
 }
 
 impl ComboItem for NoteItem {
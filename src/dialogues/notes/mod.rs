@@ -5,9 +5,9 @@ use seamonkey::*;
 use sfml::graphics::*;
 use sfml::system::Vector2f;
 
-use input::Action;
+use input::{ Action, MouseEvent };
 use themes::DialogueTheme;
-use elements::ComboBox;
+use elements::{ ComboBox, SubstringMatcher };
 use dialogues::{ DialogueMode, DialogueStatus };
 use managers::LanguageManager;
 use interface::InterfaceContext;
@@ -22,8 +22,11 @@ pub struct NotesDialogue {
 impl NotesDialogue {
 
     pub fn new(language_manager: &mut LanguageManager) -> Self {
+        let mut combobox = ComboBox::new(language_manager, "note", 0, false, Vec::new());
+        combobox.set_matcher(Box::new(SubstringMatcher { }));
+
         Self {
-            combobox: ComboBox::new(language_manager, "note", 0, false, Vec::new()),
+            combobox: combobox,
         }
     }
 
@@ -34,15 +37,15 @@ impl NotesDialogue {
     }
 
     fn update_items(&mut self, filebuffer: &Filebuffer) {
-        let items = filebuffer.get_notes().iter().map(|note| NoteItem::new(note)).collect();
+        let mut items: Vec<NoteItem> = filebuffer.get_notes().iter().map(|note| NoteItem::new(note)).collect();
+
+        items.extend(filebuffer.get_bracket_notes().iter().map(|note| NoteItem::from_bracket(note)));
+
         self.combobox.set_items(items);
     }
 
     pub fn handle_action(&mut self, interface_context: &InterfaceContext, language_manager: &mut LanguageManager, action: Action) -> DialogueStatus {
-        match action {
-            Action::Notes => return DialogueStatus::handled(),
-            action => return self.combobox.handle_action(interface_context, language_manager, action),
-        }
+        return self.combobox.handle_action(interface_context, language_manager, action);
     }
 
     pub fn get_value(&self) -> usize {
@@ -53,6 +56,14 @@ impl NotesDialogue {
         self.combobox.clear(language_manager);
     }
 
+    pub fn get_hovered(&self) -> Option<usize> {
+        return self.combobox.get_hovered();
+    }
+
+    pub fn handle_mouse(&mut self, language_manager: &mut LanguageManager, mouse_event: MouseEvent) -> Option<Action> {
+        return self.combobox.handle_mouse(language_manager, mouse_event);
+    }
+
     pub fn add_character(&mut self, language_manager: &mut LanguageManager, character: Character) {
         self.combobox.add_character(language_manager, character);
     }
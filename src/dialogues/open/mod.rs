@@ -3,7 +3,7 @@ use seamonkey::*;
 use sfml::graphics::*;
 use sfml::system::Vector2f;
 
-use input::Action;
+use input::{ Action, MouseEvent };
 use themes::DialogueTheme;
 use elements::FileBox;
 use dialogues::{ DialogueMode, DialogueStatus };
@@ -27,17 +27,27 @@ impl OpenDialogue {
         return DialogueMode::Open;
     }
 
+    pub fn reveal(&mut self, language_manager: &mut LanguageManager, file_name: &SharedString) -> DialogueMode {
+        self.filebox.reveal(language_manager, file_name);
+        return DialogueMode::Open;
+    }
+
     pub fn handle_action(&mut self, interface_context: &InterfaceContext, language_manager: &mut LanguageManager, action: Action) -> DialogueStatus {
-        match action {
-            Action::Open => return DialogueStatus::handled(),
-            action => return self.filebox.handle_action(interface_context, language_manager, action),
-        }
+        return self.filebox.handle_action(interface_context, language_manager, action);
     }
 
     pub fn get_text(&self) -> SharedString {
         return self.filebox.get_text();
     }
 
+    pub fn get_hovered(&self) -> Option<usize> {
+        return self.filebox.get_hovered();
+    }
+
+    pub fn handle_mouse(&mut self, language_manager: &mut LanguageManager, mouse_event: MouseEvent) -> Option<Action> {
+        return self.filebox.handle_mouse(language_manager, mouse_event);
+    }
+
     pub fn add_character(&mut self, language_manager: &mut LanguageManager, character: Character) {
         self.filebox.add_character(language_manager, character);
     }
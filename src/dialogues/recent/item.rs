@@ -0,0 +1,51 @@
+use seamonkey::*;
+
+use themes::{ ItemTheme, TextfieldTheme };
+use elements::ComboItem;
+use managers::RecentEntry;
+
+fn format_elapsed(seconds: u64) -> String {
+    match seconds {
+        0..=59 => return String::from("just now"),
+        60..=3599 => return format!("{} min ago", seconds / 60),
+        3600..=86399 => return format!("{} h ago", seconds / 3600),
+        _other => return format!("{} d ago", seconds / 86400),
+    }
+}
+
+#[derive(Clone)]
+pub struct RecentItem {
+    file_name: SharedString,
+    display_name: SharedString,
+}
+
+impl RecentItem {
+
+    pub fn new(entry: &RecentEntry) -> Self {
+        return Self {
+            file_name: SharedString::from(entry.file_name.as_str()),
+            display_name: format_shared!("{}   ({})", entry.file_name, format_elapsed(entry.elapsed())),
+        }
+    }
+}
+
+impl ComboItem for RecentItem {
+
+    type Value = SharedString;
+
+    fn display_name(&self) -> SharedString {
+        return self.display_name.clone();
+    }
+
+    fn update_name(&self) -> SharedString {
+        return self.file_name.clone();
+    }
+
+    fn display_theme<'t>(&self, theme: &'t ItemTheme) -> &'t TextfieldTheme {
+        return &theme.default_theme;
+    }
+
+    fn return_value(&self) -> Self::Value {
+        return self.file_name.clone();
+    }
+}
@@ -0,0 +1,71 @@
+mod item;
+
+use seamonkey::*;
+
+use sfml::graphics::*;
+use sfml::system::Vector2f;
+
+use input::{ Action, MouseEvent };
+use themes::DialogueTheme;
+use elements::ComboBox;
+use dialogues::{ DialogueMode, DialogueStatus };
+use managers::{ LanguageManager, RecentManager };
+use interface::InterfaceContext;
+
+use self::item::RecentItem;
+
+pub struct RecentDialogue {
+    combobox: ComboBox<RecentItem>,
+}
+
+impl RecentDialogue {
+
+    pub fn new(language_manager: &mut LanguageManager) -> Self {
+        Self {
+            combobox: ComboBox::new(language_manager, "recent file", 0, false, Vec::new()),
+        }
+    }
+
+    pub fn open(&mut self, recent_manager: &RecentManager, language_manager: &mut LanguageManager) -> DialogueMode {
+        self.update_items(recent_manager);
+        self.clear(language_manager);
+        return DialogueMode::Recent;
+    }
+
+    fn update_items(&mut self, recent_manager: &RecentManager) {
+        let items = recent_manager.iter().map(|entry| RecentItem::new(entry)).collect();
+        self.combobox.set_items(items);
+    }
+
+    pub fn handle_action(&mut self, interface_context: &InterfaceContext, language_manager: &mut LanguageManager, action: Action) -> DialogueStatus {
+        return self.combobox.handle_action(interface_context, language_manager, action);
+    }
+
+    pub fn get_value(&self) -> SharedString {
+        return self.combobox.get_value();
+    }
+
+    pub fn clear(&mut self, language_manager: &mut LanguageManager) {
+        self.combobox.clear(language_manager);
+    }
+
+    pub fn get_hovered(&self) -> Option<usize> {
+        return self.combobox.get_hovered();
+    }
+
+    pub fn handle_mouse(&mut self, language_manager: &mut LanguageManager, mouse_event: MouseEvent) -> Option<Action> {
+        return self.combobox.handle_mouse(language_manager, mouse_event);
+    }
+
+    pub fn add_character(&mut self, language_manager: &mut LanguageManager, character: Character) {
+        self.combobox.add_character(language_manager, character);
+    }
+
+    pub fn update_layout(&mut self, interface_context: &InterfaceContext, theme: &DialogueTheme, size: Vector2f, position: Vector2f) {
+        self.combobox.update_layout(interface_context, theme, size, position);
+    }
+
+    pub fn render(&self, framebuffer: &mut RenderTexture, interface_context: &InterfaceContext, theme: &DialogueTheme) {
+        self.combobox.render(framebuffer, interface_context, theme, true);
+    }
+}
@@ -3,7 +3,7 @@ use seamonkey::*;
 use sfml::graphics::*;
 use sfml::system::Vector2f;
 
-use input::Action;
+use input::{ Action, MouseEvent };
 use managers::LanguageManager;
 use interface::InterfaceContext;
 use dialogues::{ DialogueMode, DialogueStatus };
@@ -15,6 +15,9 @@ pub struct ReplaceDialogue {
     find_textbox: TextBox,
     replace_textbox: TextBox,
     find_focused: bool,
+    size: Vector2f,
+    position: Vector2f,
+    dialogue_height: f32,
 }
 
 impl ReplaceDialogue {
@@ -24,6 +27,9 @@ impl ReplaceDialogue {
             find_textbox: TextBox::new(language_manager, "find", 0),
             replace_textbox: TextBox::new(language_manager, "replace", 1),
             find_focused: true,
+            size: Vector2f::new(0., 0.),
+            position: Vector2f::new(0., 0.),
+            dialogue_height: 0.,
         }
     }
 
@@ -34,8 +40,8 @@ impl ReplaceDialogue {
 
     pub fn handle_action(&mut self, language_manager: &mut LanguageManager, action: Action) -> DialogueStatus {
 
-        if let Action::Replace = action {
-            return DialogueStatus::handled();
+        if let Action::Abort = action {
+            return DialogueStatus::aborted();
         }
 
         if let Action::FocusNext = action {
@@ -79,6 +85,20 @@ impl ReplaceDialogue {
         return DialogueStatus::handled(); // temp
     }
 
+    pub fn handle_mouse(&mut self, mouse_event: MouseEvent) -> Option<Action> {
+        if let MouseEvent::Pressed(position, _click_count, _add_selection) = mouse_event {
+            let relative_position = position - self.position;
+
+            if relative_position.x < 0.0 || relative_position.x > self.size.x || relative_position.y < 0.0 || relative_position.y > self.dialogue_height * 2.0 {
+                return Some(Action::Abort);
+            }
+
+            self.find_focused = relative_position.y < self.dialogue_height;
+        }
+
+        return None;
+    }
+
     pub fn add_character(&mut self, language_manager: &mut LanguageManager, character: Character) {
         match self.find_focused {
             true => self.find_textbox.add_character(language_manager, character),
@@ -96,6 +116,10 @@ impl ReplaceDialogue {
         let dialogue_height = theme.height * interface_context.font_size as f32;
         let replace_position = Vector2f::new(position.x, position.y + dialogue_height);
 
+        self.size = size;
+        self.position = position;
+        self.dialogue_height = dialogue_height;
+
         self.find_textbox.update_layout(interface_context, theme, size, position);
         self.replace_textbox.update_layout(interface_context, theme, size, replace_position);
     }
@@ -0,0 +1,42 @@
+use seamonkey::*;
+
+use themes::{ ItemTheme, TextfieldTheme };
+use elements::ComboItem;
+use input::BindingProblem;
+
+#[derive(Clone)]
+pub struct ProblemItem {
+    text: SharedString,
+    line: Option<usize>,
+}
+
+impl ProblemItem {
+
+    pub fn new(problem: &BindingProblem) -> Self {
+        return Self {
+            text: problem.display(),
+            line: problem.line,
+        }
+    }
+}
+
+impl ComboItem for ProblemItem {
+
+    type Value = Option<usize>;
+
+    fn display_name(&self) -> SharedString {
+        return self.text.clone();
+    }
+
+    fn update_name(&self) -> SharedString {
+        return self.text.clone();
+    }
+
+    fn display_theme<'t>(&self, theme: &'t ItemTheme) -> &'t TextfieldTheme {
+        return &theme.default_theme;
+    }
+
+    fn return_value(&self) -> Self::Value {
+        return self.line;
+    }
+}
@@ -5,7 +5,7 @@ use seamonkey::*;
 use sfml::graphics::*;
 use sfml::system::Vector2f;
 
-use input::Action;
+use input::{ Action, MouseEvent };
 use themes::DialogueTheme;
 use elements::ComboBox;
 use dialogues::{ DialogueMode, DialogueStatus };
@@ -21,8 +21,11 @@ pub struct ThemeDialogue {
 impl ThemeDialogue {
 
     pub fn new(language_manager: &mut LanguageManager) -> Self {
+        let mut combobox = ComboBox::new(language_manager, "theme name", 0, false, Vec::new());
+        combobox.set_recent_boost(true);
+
         Self {
-            combobox: ComboBox::new(language_manager, "theme name", 0, false, Vec::new()),
+            combobox: combobox,
         }
     }
 
@@ -44,10 +47,7 @@ impl ThemeDialogue {
     }
 
     pub fn handle_action(&mut self, interface_context: &InterfaceContext, language_manager: &mut LanguageManager, action: Action) -> DialogueStatus {
-        match action {
-            Action::Theme => return DialogueStatus::handled(),
-            action => return self.combobox.handle_action(interface_context, language_manager, action),
-        }
+        return self.combobox.handle_action(interface_context, language_manager, action);
     }
 
     pub fn get_value(&self) -> SharedString {
@@ -58,6 +58,14 @@ impl ThemeDialogue {
         self.combobox.clear(language_manager);
     }
 
+    pub fn get_hovered(&self) -> Option<usize> {
+        return self.combobox.get_hovered();
+    }
+
+    pub fn handle_mouse(&mut self, language_manager: &mut LanguageManager, mouse_event: MouseEvent) -> Option<Action> {
+        return self.combobox.handle_mouse(language_manager, mouse_event);
+    }
+
     pub fn add_character(&mut self, language_manager: &mut LanguageManager, character: Character) {
         self.combobox.add_character(language_manager, character);
     }
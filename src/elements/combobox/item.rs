@@ -1,6 +1,9 @@
 use seamonkey::SharedString;
 
+use std::cmp::Ordering;
+
 use themes::{ ItemTheme, TextfieldTheme };
+use system::subtract_or_zero;
 
 pub trait ComboItem {
 
@@ -13,4 +16,20 @@ pub trait ComboItem {
     fn display_theme<'t>(&self, theme: &'t ItemTheme) -> &'t TextfieldTheme;
 
     fn return_value(&self) -> Self::Value;
+
+    fn compare(&self, _other: &Self) -> Ordering {
+        return Ordering::Equal;
+    }
+
+    fn display_positions(&self, positions: &[usize]) -> Vec<usize> {
+        let display_name = self.display_name();
+        let update_name = self.update_name();
+
+        if let Some(offset) = display_name.position(&update_name).first() {
+            return positions.iter().map(|position| position + offset).collect();
+        }
+
+        let offset = subtract_or_zero(update_name.len(), display_name.len());
+        return positions.iter().filter(|position| **position >= offset).map(|position| position - offset).collect();
+    }
 }
@@ -0,0 +1,88 @@
+use system::{ FuzzyMatch, fuzzy_match };
+
+pub trait Matcher {
+
+    fn find_match(&self, pattern: &[char], text: &[char]) -> Option<FuzzyMatch>;
+}
+
+pub struct FuzzyMatcher { }
+
+impl Matcher for FuzzyMatcher {
+
+    fn find_match(&self, pattern: &[char], text: &[char]) -> Option<FuzzyMatch> {
+        return fuzzy_match(pattern, text);
+    }
+}
+
+pub struct SubstringMatcher { }
+
+impl Matcher for SubstringMatcher {
+
+    fn find_match(&self, pattern: &[char], text: &[char]) -> Option<FuzzyMatch> {
+        if pattern.is_empty() {
+            return Some(FuzzyMatch { score: 0, positions: Vec::new() });
+        }
+
+        if pattern.len() > text.len() {
+            return None;
+        }
+
+        let case_sensitive = pattern.iter().any(|character| character.is_uppercase());
+
+        for start in 0..=text.len() - pattern.len() {
+            let matches = pattern.iter().zip(text[start..].iter()).all(|(left, right)| match case_sensitive {
+                true => left == right,
+                false => left.to_lowercase().eq(right.to_lowercase()),
+            });
+
+            if matches {
+                return Some(FuzzyMatch {
+                    score: -(start as i64),
+                    positions: (start..start + pattern.len()).collect(),
+                });
+            }
+        }
+
+        return None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn find(matcher: &dyn Matcher, pattern: &str, text: &str) -> Option<FuzzyMatch> {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let text: Vec<char> = text.chars().collect();
+        return matcher.find_match(&pattern, &text);
+    }
+
+    #[test]
+    fn substring_positions() {
+        let substring_match = find(&SubstringMatcher { }, "bar", "foobar").unwrap();
+        assert_eq!(substring_match.positions, vec![3, 4, 5]);
+        assert_eq!(substring_match.score, -3);
+    }
+
+    #[test]
+    fn substring_requires_contiguous_text() {
+        assert!(find(&SubstringMatcher { }, "fb", "foobar").is_none());
+        assert!(find(&SubstringMatcher { }, "foobars", "foobar").is_none());
+        assert!(find(&FuzzyMatcher { }, "fb", "foobar").is_some());
+    }
+
+    #[test]
+    fn substring_ranking() {
+        let early = find(&SubstringMatcher { }, "ab", "abc").unwrap();
+        let late = find(&SubstringMatcher { }, "ab", "cab").unwrap();
+        assert!(early.score > late.score);
+    }
+
+    #[test]
+    fn substring_smart_case() {
+        assert!(find(&SubstringMatcher { }, "bar", "FooBar").is_some());
+        assert!(find(&SubstringMatcher { }, "Bar", "foobar").is_none());
+        assert!(find(&SubstringMatcher { }, "Bar", "FooBar").is_some());
+    }
+}
@@ -1,21 +1,29 @@
 mod selection;
 mod item;
+mod matcher;
 
 use seamonkey::*;
 
+use std::cmp::{ Ordering, min };
+use std::mem::replace;
+
 use sfml::graphics::*;
 use sfml::system::Vector2f;
 
-use input::Action;
+use input::{ Action, MouseEvent };
 use themes::{ InterfaceTheme, DialogueTheme, ItemTheme };
 use elements::{ TextBox, Textfield };
 use dialogues::DialogueStatus;
 use managers::LanguageManager;
 use interface::InterfaceContext;
-use system::subtract_or_zero;
+use system::{ FuzzyMatch, subtract_or_zero };
 
 pub use self::selection::ComboSelection;
 pub use self::item::ComboItem;
+pub use self::matcher::{ Matcher, FuzzyMatcher, SubstringMatcher };
+
+const RECENT_BOOST: i64 = 8;
+const RECENT_LIMIT: usize = 16;
 
 macro_rules! handle_return_none {
     ($expression: expr) => ({
@@ -42,12 +50,20 @@ pub struct ComboBox<I: ComboItem + Clone> {
     size: Vector2f,
     position: Vector2f,
     line_count: usize,
+    matcher: Box<dyn Matcher>,
+    recent_boost: bool,
+    recently_used: Vec<SharedString>,
+    matched: Vec<(I, FuzzyMatch)>,
+    pattern: Vec<char>,
+    hovered: Option<usize>,
+    item_top: f32,
+    element_height: f32,
 }
 
 impl<I: ComboItem + Clone> ComboBox<I> {
 
     pub fn new(language_manager: &mut LanguageManager, description: &'static str, displacement: usize, allow_unknown: bool, items: Vec<I>) -> Self {
-        Self {
+        let mut combobox = Self {
             textbox: TextBox::new(language_manager, description, displacement),
             allow_unknown: allow_unknown,
             items: items,
@@ -57,6 +73,42 @@ impl<I: ComboItem + Clone> ComboBox<I> {
             size: Vector2f::new(0., 0.),
             position: Vector2f::new(0., 0.),
             line_count: 0,
+            matcher: Box::new(FuzzyMatcher { }),
+            recent_boost: false,
+            recently_used: Vec::new(),
+            matched: Vec::new(),
+            pattern: Vec::new(),
+            hovered: None,
+            item_top: 0.,
+            element_height: 1.,
+        };
+
+        combobox.rescore_items();
+        return combobox;
+    }
+
+    pub fn set_matcher(&mut self, matcher: Box<dyn Matcher>) {
+        self.matcher = matcher;
+        self.rescore_items();
+    }
+
+    pub fn set_recent_boost(&mut self, recent_boost: bool) {
+        self.recent_boost = recent_boost;
+    }
+
+    fn record_use(&mut self, name: SharedString) {
+        if self.recent_boost {
+            self.recently_used.retain(|used| *used != name);
+            self.recently_used.insert(0, name);
+            self.recently_used.truncate(RECENT_LIMIT);
+            self.rescore_items();
+        }
+    }
+
+    fn recent_score(&self, name: &SharedString) -> i64 {
+        match self.recently_used.iter().position(|used| used == name) {
+            Some(index) => return RECENT_BOOST * (RECENT_LIMIT - index) as i64,
+            None => return 0,
         }
     }
 
@@ -67,10 +119,9 @@ impl<I: ComboItem + Clone> ComboBox<I> {
                 self.textbox.set_text_without_save(language_manager, original);
             } else {
                 let new_index = index - 1;
-                let valid_items = self.valid_items();
                 self.selection = ComboSelection::Item(new_index, original.clone());
 
-                let text = valid_items[new_index].update_name();
+                let text = self.matched[new_index].0.update_name();
                 self.textbox.set_text_without_save(language_manager, text);
                 self.check_selection_gaps(interface_context, new_index);
             }
@@ -79,11 +130,9 @@ impl<I: ComboItem + Clone> ComboBox<I> {
 
     fn move_down(&mut self, interface_context: &InterfaceContext, language_manager: &mut LanguageManager) {
         if let ComboSelection::TextBox = self.selection.clone() {
-            let valid_items = self.valid_items();
-
-            if !valid_items.is_empty() {
+            if !self.matched.is_empty() {
                 self.selection = ComboSelection::Item(0, self.textbox.get_text());
-                let text = valid_items[0].update_name();
+                let text = self.matched[0].0.update_name();
                 self.textbox.set_text_without_save(language_manager, text);
             }
 
@@ -91,11 +140,9 @@ impl<I: ComboItem + Clone> ComboBox<I> {
         }
 
         if let ComboSelection::Item(index, original) = self.selection.clone() {
-            let valid_items = self.valid_items();
-
-            if index + 1 < valid_items.len() {
+            if index + 1 < self.matched.len() {
                 self.selection = ComboSelection::Item(index + 1, original);
-                let text = valid_items[index + 1].update_name();
+                let text = self.matched[index + 1].0.update_name();
                 self.textbox.set_text_without_save(language_manager, text);
                 self.check_selection_gaps(interface_context, index + 1);
             }
@@ -114,40 +161,136 @@ impl<I: ComboItem + Clone> ComboBox<I> {
 
     pub fn set_items(&mut self, items: Vec<I>) {
         self.items = items;
+        self.hovered = None;
+        self.rescore_items();
         // cap selection !!!!!!
     }
 
-    pub fn valid_items(&self) -> Vec<I> {
-        let mut valid_items = self.items.clone();
-        let original = match &self.selection {
-            ComboSelection::Item(_index, original) => original.clone(),
-            ComboSelection::TextBox => self.textbox.get_text(),
+    pub fn add_items(&mut self, items: Vec<I>) {
+        let matched_items = self.score_items(&self.pattern, items.iter());
+        let selected_index = match &self.selection {
+            ComboSelection::Item(index, _original) => Some(*index),
+            ComboSelection::TextBox => None,
         };
-        valid_items.retain(|item| item.update_name().contains(&original));
-        return valid_items;
+
+        let previous_items = replace(&mut self.matched, Vec::with_capacity(self.matched.len() + matched_items.len()));
+        let mut previous_items = previous_items.into_iter().peekable();
+        let mut new_items = matched_items.into_iter().peekable();
+        let mut previous_index = 0;
+        let mut inserted_before = 0;
+
+        loop {
+            let take_new = match (previous_items.peek(), new_items.peek()) {
+                (Some(previous), Some(new)) => Self::compare_matches(new, previous) == Ordering::Less,
+                (None, Some(_new)) => true,
+                (_previous, None) => false,
+            };
+
+            match take_new {
+
+                true => {
+                    if selected_index.map_or(false, |index| previous_index <= index) {
+                        inserted_before += 1;
+                    }
+                    self.matched.push(new_items.next().unwrap());
+                },
+
+                false => match previous_items.next() {
+                    Some(previous) => {
+                        self.matched.push(previous);
+                        previous_index += 1;
+                    },
+                    None => break,
+                },
+            }
+        }
+
+        // keep the selected row in place while the rest of the list grows around it
+        if let ComboSelection::Item(index, original) = self.selection.clone() {
+            self.selection = ComboSelection::Item(index + inserted_before, original);
+            self.scroll += inserted_before;
+        }
+
+        self.items.extend(items);
     }
 
-    pub fn remove_selected_item(&mut self, interface_context: &InterfaceContext, language_manager: &mut LanguageManager) {
-        if let ComboSelection::Item(index, original) = &self.selection {
-            self.items.remove(*index);
+    fn score_items<'a>(&self, pattern: &[char], items: impl Iterator<Item = &'a I>) -> Vec<(I, FuzzyMatch)> where I: 'a {
+        let mut matched_items = Vec::new();
+
+        for item in items {
+            let name = item.update_name();
+            let text: Vec<char> = name.serialize().chars().collect();
+
+            if let Some(mut fuzzy_match) = self.matcher.find_match(pattern, &text) {
+                fuzzy_match.score += self.recent_score(&name);
+                matched_items.push((item.clone(), fuzzy_match));
+            }
         }
 
+        Self::sort_matches(&mut matched_items);
+        return matched_items;
+    }
+
+    fn compare_matches(left: &(I, FuzzyMatch), right: &(I, FuzzyMatch)) -> Ordering {
+        match right.1.score.cmp(&left.1.score) {
+            Ordering::Equal => return left.0.compare(&right.0),
+            ordering => return ordering,
+        }
+    }
+
+    fn sort_matches(matched_items: &mut Vec<(I, FuzzyMatch)>) {
+        matched_items.sort_by(Self::compare_matches);
+    }
+
+    fn rescore_items(&mut self) {
+        self.pattern = self.get_original().serialize().chars().collect();
+        self.matched = self.score_items(&self.pattern, self.items.iter());
+    }
+
+    fn update_matches(&mut self) {
+        let pattern: Vec<char> = self.get_original().serialize().chars().collect();
+
+        if pattern == self.pattern {
+            return;
+        }
+
+        // a longer query can only match a subset of what the shorter one matched
+        match !self.pattern.is_empty() && pattern.starts_with(&self.pattern) {
+            true => self.matched = self.score_items(&pattern, self.matched.iter().map(|(item, _fuzzy_match)| item)),
+            false => self.matched = self.score_items(&pattern, self.items.iter()),
+        }
+
+        self.pattern = pattern;
+        self.hovered = None;
+    }
+
+    pub fn matched_items(&self) -> &[(I, FuzzyMatch)] {
+        return &self.matched;
+    }
+
+    pub fn valid_items(&self) -> Vec<I> {
+        return self.matched.iter().map(|(item, _fuzzy_match)| item.clone()).collect();
+    }
+
+    pub fn remove_selected_item(&mut self, interface_context: &InterfaceContext, language_manager: &mut LanguageManager) {
         if let ComboSelection::Item(index, original) = self.selection.clone() {
-            let valid_items = self.valid_items();
+            let name = self.matched[index].0.update_name();
+            self.items.retain(|item| item.update_name() != name);
+            self.matched.remove(index);
 
-            if valid_items.is_empty() {
+            if self.matched.is_empty() {
                 self.selection = ComboSelection::TextBox;
                 self.textbox.set_text_without_save(language_manager, original);
                 return;
             }
 
-            let new_index = match index >= valid_items.len() {
+            let new_index = match index >= self.matched.len() {
                 true => index - 1,
                 false => index,
             };
 
             self.selection = ComboSelection::Item(new_index, original.clone());
-            let text = valid_items[new_index].update_name();
+            let text = self.matched[new_index].0.update_name();
             self.textbox.set_text_without_save(language_manager, text);
             self.check_selection_gaps(interface_context, new_index);
         }
@@ -179,27 +322,29 @@ impl<I: ComboItem + Clone> ComboBox<I> {
 
     pub fn set_text(&mut self, language_manager: &mut LanguageManager, text: SharedString) {
         self.textbox.set_text(language_manager, text);
+        self.update_matches();
     }
 
     pub fn clear(&mut self, language_manager: &mut LanguageManager) {
         self.reset_selection();
         self.textbox.clear(language_manager);
+        self.update_matches();
     }
 
     pub fn reset_selection(&mut self) {
         self.selection = ComboSelection::TextBox;
         self.scroll = 0;
+        self.update_matches();
     }
 
     fn focus_next(&mut self, language_manager: &mut LanguageManager) -> bool {
-        let valid_items = self.valid_items();
-        if valid_items.is_empty() {
+        if self.matched.is_empty() {
             return false;
         }
 
         let suffix = match &self.selection {
-            ComboSelection::Item(index, _original) => valid_items[*index].update_name(),
-            ComboSelection::TextBox => valid_items[0].update_name(),
+            ComboSelection::Item(index, _original) => self.matched[*index].0.update_name(),
+            ComboSelection::TextBox => self.matched[0].0.update_name(),
         };
 
         self.textbox.set_text(language_manager, suffix);
@@ -209,14 +354,16 @@ impl<I: ComboItem + Clone> ComboBox<I> {
 
     fn handle_confirm(&mut self, language_manager: &mut LanguageManager) -> DialogueStatus {
         if !self.allow_unknown && self.selection.is_textbox() {
-            let valid_items = self.valid_items();
-
-            if valid_items.is_empty() {
+            if self.matched.is_empty() {
                 return DialogueStatus::handled();
             }
 
-            self.textbox.set_text(language_manager, valid_items[0].update_name());
+            let text = self.matched[0].0.update_name();
+            self.textbox.set_text(language_manager, text);
         }
+
+        let name = self.textbox.get_text();
+        self.record_use(name);
         return DialogueStatus::completed();
     }
 
@@ -264,7 +411,10 @@ impl<I: ComboItem + Clone> ComboBox<I> {
             _other => { },
         }
 
-        if let Some(action) = self.textbox.handle_action(language_manager, action) {
+        let action = self.textbox.handle_action(language_manager, action);
+        self.update_matches();
+
+        if let Some(action) = action {
             match action {
 
                 Action::Confirm => return self.handle_confirm(language_manager),
@@ -278,9 +428,82 @@ impl<I: ComboItem + Clone> ComboBox<I> {
         return DialogueStatus::handled();
     }
 
+    fn item_at(&self, position: Vector2f) -> Option<usize> {
+        if position.x < self.position.x || position.x > self.position.x + self.size.x || position.y < self.item_top {
+            return None;
+        }
+
+        let line = ((position.y - self.item_top) / self.element_height) as usize;
+        let index = self.scroll + line;
+
+        match line < self.line_count && index < self.matched.len() {
+            true => return Some(index),
+            false => return None,
+        }
+    }
+
+    fn contains(&self, position: Vector2f) -> bool {
+        let visible_items = min(subtract_or_zero(self.matched.len(), self.scroll), self.line_count);
+        let bottom = self.item_top + visible_items as f32 * self.element_height;
+        return position.x >= self.position.x && position.x <= self.position.x + self.size.x && position.y >= self.position.y && position.y <= bottom;
+    }
+
+    fn select_item(&mut self, language_manager: &mut LanguageManager, index: usize) {
+        let original = self.get_original();
+        let text = self.matched[index].0.update_name();
+        self.selection = ComboSelection::Item(index, original);
+        self.textbox.set_text_without_save(language_manager, text);
+    }
+
+    fn scroll_items(&mut self, delta: f32) {
+        let lines = delta.abs().max(1.0) as usize;
+        let last_scroll = subtract_or_zero(self.matched.len(), self.line_count);
+
+        match delta > 0.0 {
+            true => self.scroll = subtract_or_zero(self.scroll, lines),
+            false => self.scroll = min(self.scroll + lines, last_scroll),
+        }
+
+        self.hovered = None;
+    }
+
+    pub fn get_hovered(&self) -> Option<usize> {
+        return self.hovered;
+    }
+
+    pub fn handle_mouse(&mut self, language_manager: &mut LanguageManager, mouse_event: MouseEvent) -> Option<Action> {
+        match mouse_event {
+
+            MouseEvent::Moved(position) => self.hovered = self.item_at(position),
+
+            MouseEvent::Pressed(position, click_count, _add_selection) => {
+                if !self.contains(position) {
+                    return Some(Action::Abort);
+                }
+
+                if let Some(index) = self.item_at(position) {
+                    self.select_item(language_manager, index);
+
+                    if click_count > 1 {
+                        return Some(Action::Confirm);
+                    }
+                }
+            },
+
+            MouseEvent::Released => { },
+
+            MouseEvent::Scrolled(delta) => self.scroll_items(delta),
+
+            MouseEvent::ScrolledHorizontally(..) => { },
+        }
+
+        return None;
+    }
+
     pub fn add_character(&mut self, language_manager: &mut LanguageManager, character: Character) {
         self.reset_selection();
         self.textbox.add_character(language_manager, character);
+        self.update_matches();
     }
 
     pub fn update_layout(&mut self, interface_context: &InterfaceContext, theme: &DialogueTheme, size: Vector2f, position: Vector2f) {
@@ -293,6 +516,8 @@ impl<I: ComboItem + Clone> ComboBox<I> {
         self.line_count = (height / element_height) as usize;
         self.size = size;
         self.position = position;
+        self.element_height = element_height;
+        self.item_top = position.y + theme.focused_textbox_theme.padding * float_font_size + (self.displacement + 1) as f32 * theme.height * float_font_size;
 
         if let ComboSelection::Item(index, ..) = self.selection.clone() {
             self.check_selection_gaps(interface_context, index);
@@ -312,20 +537,25 @@ impl<I: ComboItem + Clone> ComboBox<I> {
             let dialogue_height = theme.height * interface_context.font_size as f32;
             let mut top_position = self.position.y + padding + (self.displacement + 1) as f32 * dialogue_height;
             let size = Vector2f::new(self.size.x, dialogue_height);
-            let valid_items = self.valid_items();
+            let matched_items = &self.matched;
 
-            for index in self.scroll..valid_items.len() {
+            for index in self.scroll..matched_items.len() {
                 if top_position > self.size.y || index - self.scroll >= self.line_count {
                     break;
                 }
 
-                let item_theme = match self.selection.index_matches(index) {
-                    true => &theme.focused_item_theme,
-                    false => &theme.unfocused_item_theme,
+                let item_theme = if self.selection.index_matches(index) {
+                    &theme.focused_item_theme
+                } else if self.hovered == Some(index) {
+                    &theme.hovered_item_theme
+                } else {
+                    &theme.unfocused_item_theme
                 };
 
+                let (item, fuzzy_match) = &matched_items[index];
                 let position = Vector2f::new(self.position.x, top_position);
-                Textfield::render(framebuffer, interface_context, valid_items[index].display_theme(item_theme), &valid_items[index].display_name(), size, position, dialogue_height);
+                let positions = item.display_positions(&fuzzy_match.positions);
+                Textfield::render_matched(framebuffer, interface_context, item.display_theme(item_theme), &item_theme.matched_theme, &item.display_name(), &positions, size, position, dialogue_height);
                 top_position += dialogue_height + item_theme.padding * interface_context.font_size as f32;
             }
         }
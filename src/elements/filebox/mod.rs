@@ -5,7 +5,7 @@ use seamonkey::*;
 use sfml::graphics::*;
 use sfml::system::Vector2f;
 
-use input::Action;
+use input::{ Action, MouseEvent };
 use themes::DialogueTheme;
 use elements::{ ComboBox, ComboSelection, ComboItem };
 use dialogues::DialogueStatus;
@@ -72,6 +72,18 @@ impl FileBox {
         return self.combobox.get_text().position(&SharedString::from("/")).len();
     }
 
+    pub fn reveal(&mut self, language_manager: &mut LanguageManager, path: &SharedString) {
+        let positions = path.position(&SharedString::from("/"));
+
+        match positions.last() {
+            Some(position) => self.combobox.set_text(language_manager, path.slice(0, *position)),
+            None => self.combobox.clear(language_manager),
+        }
+
+        self.combobox.reset_selection();
+        self.reload(language_manager);
+    }
+
     pub fn reload(&mut self, language_manager: &mut LanguageManager) {
         self.update_entries(language_manager, self.get_combined());
         self.directories = self.directory_count();
@@ -185,6 +197,14 @@ impl FileBox {
         return return_value;
     }
 
+    pub fn get_hovered(&self) -> Option<usize> {
+        return self.combobox.get_hovered();
+    }
+
+    pub fn handle_mouse(&mut self, language_manager: &mut LanguageManager, mouse_event: MouseEvent) -> Option<Action> {
+        return self.combobox.handle_mouse(language_manager, mouse_event);
+    }
+
     pub fn add_character(&mut self, language_manager: &mut LanguageManager, character: Character) {
         self.combobox.add_character(language_manager, character);
         self.check_directories(language_manager);
@@ -5,28 +5,57 @@ use sfml::system::Vector2f;
 
 use themes::TextTheme;
 use interface::InterfaceContext;
+use system::character_width;
 
 pub struct Text { }
 
 impl Text {
 
     pub fn render(framebuffer: &mut RenderTexture, interface_context: &InterfaceContext, theme: &TextTheme, text: &SharedString, size: Vector2f, position: Vector2f) {
+        Self::render_matched(framebuffer, interface_context, theme, theme, text, &[], size, position);
+    }
 
-        let character_scaling = interface_context.character_spacing * interface_context.font_size as f32;
-
-        let mut character = sfml::graphics::Text::default();
-        character.set_font(&interface_context.font);
-        character.set_character_size(interface_context.font_size as u32);
+    fn apply_theme(character: &mut sfml::graphics::Text, interface_context: &InterfaceContext, theme: &TextTheme) {
         character.set_outline_thickness(interface_context.font_size as f32 * theme.border_width);
         character.set_outline_color(theme.border_color);
         character.set_fill_color(theme.text_color);
         character.set_style(theme.text_style);
-        character.set_position(position);
+    }
+
+    pub fn render_matched(framebuffer: &mut RenderTexture, interface_context: &InterfaceContext, theme: &TextTheme, matched_theme: &TextTheme, text: &SharedString, matched: &[usize], size: Vector2f, position: Vector2f) {
+
+        let character_scaling = interface_context.character_spacing * interface_context.font_size as f32;
+
+        let mut character = sfml::graphics::Text::default();
+        character.set_character_size(interface_context.font_size as u32);
+        Self::apply_theme(&mut character, interface_context, theme);
+
+        let mut left_offset = 0.0;
+        let mut base_offset = 0.0;
 
         for index in 0..text.len() {
+            if !matched.is_empty() {
+                match matched.contains(&index) {
+                    true => Self::apply_theme(&mut character, interface_context, matched_theme),
+                    false => Self::apply_theme(&mut character, interface_context, theme),
+                }
+            }
+
+            let width = character_width(text[index]);
+            let character_offset = match width {
+                0 => base_offset,
+                _other => left_offset,
+            };
+
+            character.set_font(interface_context.font_for(text[index]));
             character.set_string(&format!("{}", text[index]));
+            character.set_position(position + Vector2f::new(character_offset, 0.0));
             framebuffer.draw(&character);
-            character.move_(Vector2f::new(character_scaling, 0.0));
+
+            if width != 0 {
+                base_offset = left_offset;
+                left_offset += width as f32 * character_scaling;
+            }
         }
     }
 }
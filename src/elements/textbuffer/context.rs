@@ -1,6 +1,10 @@
+const SMALLEST_TAB_WIDTH: usize = 1;
+const BIGGEST_TAB_WIDTH: usize = 16;
+
 pub struct TextbufferContext {
     pub line_numbers: bool,
     pub tab_width: usize,
+    pub tab_width_adjusted: bool,
     pub scroll_size: usize,
     pub append_lines: bool,
     pub status_bar: bool,
@@ -11,7 +15,11 @@ pub struct TextbufferContext {
     pub start_at_symbol: bool,
     pub relative_line_numbers: bool,
     pub multiline: bool,
+    pub modal: bool,
+    pub soft_wrap: bool,
+    pub auto_close: bool,
     pub selection_gap: usize,
+    pub horizontal_gap: usize,
 }
 
 impl TextbufferContext {
@@ -20,6 +28,7 @@ impl TextbufferContext {
         return Self {
             line_numbers: true,
             tab_width: 4,
+            tab_width_adjusted: false,
             scroll_size: 8,
             append_lines: false,
             status_bar: true,
@@ -30,7 +39,11 @@ impl TextbufferContext {
             start_at_symbol: true,
             relative_line_numbers: false,
             multiline: true,
+            modal: false,
+            soft_wrap: false,
+            auto_close: true,
             selection_gap: 8,
+            horizontal_gap: 8,
         }
     }
 
@@ -38,6 +51,7 @@ impl TextbufferContext {
         return Self {
             line_numbers: false,
             tab_width: 4,
+            tab_width_adjusted: false,
             scroll_size: 0,
             append_lines: false,
             status_bar: false,
@@ -48,13 +62,31 @@ impl TextbufferContext {
             start_at_symbol: false,
             relative_line_numbers: false,
             multiline: false,
+            modal: false,
+            soft_wrap: false,
+            auto_close: false,
             selection_gap: 0,
+            horizontal_gap: 0,
         }
     }
 
-    // increase_tab_width
+    pub fn increase_tab_width(&mut self) -> bool {
+        if self.tab_width < BIGGEST_TAB_WIDTH {
+            self.tab_width += 1;
+            self.tab_width_adjusted = true;
+            return true;
+        }
+        return false;
+    }
 
-    // decrease_tab_width
+    pub fn decrease_tab_width(&mut self) -> bool {
+        if self.tab_width > SMALLEST_TAB_WIDTH {
+            self.tab_width -= 1;
+            self.tab_width_adjusted = true;
+            return true;
+        }
+        return false;
+    }
 
     // increase_scroll_size
 
@@ -107,4 +139,16 @@ impl TextbufferContext {
     pub fn toggle_relative_line_numbers(&mut self) {
         self.relative_line_numbers = !self.relative_line_numbers;
     }
+
+    pub fn toggle_modal(&mut self) {
+        self.modal = !self.modal;
+    }
+
+    pub fn toggle_soft_wrap(&mut self) {
+        self.soft_wrap = !self.soft_wrap;
+    }
+
+    pub fn toggle_auto_close(&mut self) {
+        self.auto_close = !self.auto_close;
+    }
 }
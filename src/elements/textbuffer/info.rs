@@ -3,6 +3,9 @@ pub struct LineInfo {
     pub number: usize,
     pub index: usize,
     pub length: usize,
+    pub indent: usize,
+    pub continued: bool,
+    pub wrapped: bool,
     pub highlighted: bool
 }
 
@@ -13,7 +16,75 @@ impl LineInfo {
             number: number,
             index: index,
             length: length,
+            indent: 0,
+            continued: false,
+            wrapped: false,
             highlighted: false,
         }
     }
+
+    pub fn last_index(&self) -> usize {
+        match self.wrapped {
+            true => return self.index + self.length - 1,
+            false => return self.index + self.length,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct VisualRow {
+    pub index: usize,
+    pub end: usize,
+    pub indent: usize,
+}
+
+impl VisualRow {
+
+    pub fn new(index: usize, end: usize, indent: usize) -> Self {
+        return Self {
+            index: index,
+            end: end,
+            indent: indent,
+        }
+    }
+}
+
+pub struct RowCache {
+    pub key: Option<(usize, usize, usize)>,
+    pub line_starts: Vec<usize>,
+    pub row_starts: Vec<usize>,
+    pub row_count: usize,
+}
+
+impl RowCache {
+
+    pub fn new() -> Self {
+        return Self {
+            key: None,
+            line_starts: Vec::new(),
+            row_starts: Vec::new(),
+            row_count: 0,
+        }
+    }
+
+    pub fn line_at_index(&self, index: usize) -> usize {
+        match self.line_starts.binary_search(&index) {
+            Ok(line) => return line,
+            Err(line) => return line - 1,
+        }
+    }
+
+    pub fn line_at_row(&self, row: usize) -> usize {
+        match self.row_starts.binary_search(&row) {
+            Ok(line) => return line,
+            Err(line) => return line - 1,
+        }
+    }
+
+    pub fn rows_in_line(&self, line: usize) -> usize {
+        match self.row_starts.get(line + 1) {
+            Some(next_start) => return next_start - self.row_starts[line],
+            None => return self.row_count - self.row_starts[line],
+        }
+    }
 }
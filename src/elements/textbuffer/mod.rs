@@ -1,12 +1,14 @@
 mod word;
 mod info;
 mod context;
+mod mode;
 
 use seamonkey::*;
 use seamonkey::tokenize::Tokenizer;
 //use parse::parse;
 
 use std::cmp::{ min, max };
+use std::cell::{ Ref, RefCell };
 
 use sfml::graphics::RenderTexture;
 use sfml::system::Vector2f;
@@ -16,13 +18,16 @@ use themes::{ TextbufferTheme, StatusBarTheme };
 use interface::InterfaceContext;
 use selection::{ Selection, SelectionMode };
 use filebuffer::{ Filebuffer, BufferAction };
-use managers::LanguageManager;
+use managers::{ LanguageManager, BufferPosition };
 use elements::{ Text, Field, Textfield };
-use system::subtract_or_zero;
+use system::{ subtract_or_zero, character_width };
 
 pub use self::word::Word;
 pub use self::info::LineInfo;
+
+use self::info::{ VisualRow, RowCache };
 pub use self::context::TextbufferContext;
+pub use self::mode::EditMode;
 
 macro_rules! handle_return {
     ($expression: expr) => ({
@@ -42,7 +47,28 @@ pub struct Textbuffer {
     horizontal_scroll: usize,
     history_index: usize,
     line_count: usize,
+    column_count: usize,
+    wrap_columns: Option<usize>,
+    tab_width: usize,
+    tab_width_adjusted: bool,
+    row_cache: RefCell<RowCache>,
     window_id: usize,
+    drag_anchor: Option<(usize, usize)>,
+    status_hint: Option<SharedString>,
+    recording: bool,
+    edit_mode: EditMode,
+}
+
+fn shift_index(index: usize, start: usize, end: usize, new_length: usize) -> usize {
+    if index < start {
+        return index;
+    }
+
+    if index >= end {
+        return index + new_length - (end - start);
+    }
+
+    return start + min(index - start, new_length);
 }
 
 impl Textbuffer {
@@ -59,7 +85,16 @@ impl Textbuffer {
             horizontal_scroll: 0,
             history_index: 0,
             line_count: 1,
+            column_count: 0,
+            wrap_columns: None,
+            tab_width: 4,
+            tab_width_adjusted: false,
+            row_cache: RefCell::new(RowCache::new()),
             window_id: window_id,
+            drag_anchor: None,
+            status_hint: None,
+            recording: false,
+            edit_mode: EditMode::Normal,
         }
     }
 
@@ -79,18 +114,58 @@ impl Textbuffer {
         return self.selections.clone();
     }
 
+    pub fn set_selections(&mut self, textbuffer_context: &TextbufferContext, filebuffer: &mut Filebuffer, selections: Vec<Selection>) {
+        while !self.selections.is_empty() {
+            let index = self.selections.len() - 1;
+            self.remove_selection(filebuffer, index);
+        }
+
+        for selection in selections.into_iter() {
+            self.add_selection_(filebuffer, selection);
+        }
+
+        self.adding_selection = false;
+        self.check_selection_gaps(textbuffer_context, filebuffer);
+    }
+
     pub fn update_layout(&mut self, interface_context: &InterfaceContext, textbuffer_context: &TextbufferContext, filebuffer: &Filebuffer, size: Vector2f) {
         let line_scaling = interface_context.line_spacing * interface_context.font_size as f32;
         self.line_count = (size.y / line_scaling) as usize;
+        self.tab_width = textbuffer_context.tab_width;
+        self.tab_width_adjusted = textbuffer_context.tab_width_adjusted;
         self.size = size;
 
         self.check_selection_gaps(textbuffer_context, filebuffer);
     }
 
+    pub fn set_columns(&mut self, column_count: usize, soft_wrap: bool) {
+        self.column_count = column_count;
+        self.wrap_columns = match soft_wrap {
+            true => Some(column_count),
+            false => None,
+        };
+    }
+
     pub fn set_position(&mut self, position: Vector2f) {
         self.position = position;
     }
 
+    pub fn set_status_hint(&mut self, status_hint: Option<SharedString>) {
+        self.status_hint = status_hint;
+    }
+
+    pub fn set_recording(&mut self, recording: bool) {
+        self.recording = recording;
+    }
+
+    pub fn get_edit_mode(&self) -> EditMode {
+        return self.edit_mode;
+    }
+
+    pub fn set_edit_mode(&mut self, edit_mode: EditMode) {
+        self.edit_mode = edit_mode;
+    }
+
     fn set_selection_mode(&mut self, filebuffer: &mut Filebuffer, mode: SelectionMode) {
         self.history_index = filebuffer.change_selection_mode(self.window_id, self.mode, mode, true);
         self.mode = mode;
@@ -106,10 +181,37 @@ impl Textbuffer {
 
         self.selections = vec![Selection::new(0, 0, 0)];
         self.adding_selection = false;
+        self.drag_anchor = None;
         self.character_mode(filebuffer);
         self.history_index = filebuffer.get_history_index();
     }
 
+    pub fn get_position(&self) -> BufferPosition {
+        return BufferPosition {
+            selections: self.selections.clone(),
+            mode: self.mode,
+            vertical_scroll: self.vertical_scroll,
+            horizontal_scroll: self.horizontal_scroll,
+        }
+    }
+
+    pub fn restore_position(&mut self, textbuffer_context: &TextbufferContext, filebuffer: &mut Filebuffer, position: &BufferPosition) {
+        let last_index = filebuffer.last_buffer_index();
+
+        self.selections = position.selections.clone();
+        self.selections.iter_mut().for_each(|selection| {
+            selection.primary_index = min(selection.primary_index, last_index);
+            selection.secondary_index = min(selection.secondary_index, last_index);
+        });
+
+        self.mode = position.mode;
+        self.adding_selection = false;
+        self.vertical_scroll = position.vertical_scroll;
+        self.horizontal_scroll = position.horizontal_scroll;
+        self.history_index = filebuffer.get_history_index();
+        self.check_bottom_scroll(textbuffer_context, filebuffer);
+    }
+
     pub fn scroll_up(&mut self, textbuffer_context: &TextbufferContext) {
         match self.vertical_scroll >= textbuffer_context.scroll_size {
             true => self.vertical_scroll -= textbuffer_context.scroll_size,
@@ -122,8 +224,44 @@ impl Textbuffer {
         self.check_bottom_scroll(textbuffer_context, filebuffer);
     }
 
+    pub fn scroll_left(&mut self, textbuffer_context: &TextbufferContext) {
+        self.horizontal_scroll = subtract_or_zero(self.horizontal_scroll, textbuffer_context.scroll_size);
+    }
+
+    pub fn scroll_right(&mut self, textbuffer_context: &TextbufferContext, filebuffer: &Filebuffer) {
+        if self.wrap_columns.is_some() {
+            return;
+        }
+
+        let widest_row = self.line_info(textbuffer_context, filebuffer).iter().map(|line| self.row_width(filebuffer, line)).max().unwrap_or(0);
+        let last_scroll = subtract_or_zero(widest_row + textbuffer_context.horizontal_gap, self.column_count);
+        self.horizontal_scroll = min(self.horizontal_scroll + textbuffer_context.scroll_size, max(last_scroll, self.horizontal_scroll));
+    }
+
+    fn row_width(&self, filebuffer: &Filebuffer, line: &LineInfo) -> usize {
+        return self.column_after(filebuffer, line.index, line.index + line.length, line.indent);
+    }
+
+    fn check_horizontal_gap(&mut self, textbuffer_context: &TextbufferContext, filebuffer: &Filebuffer) {
+        if self.wrap_columns.is_some() || self.column_count == 0 {
+            self.horizontal_scroll = 0;
+            return;
+        }
+
+        let selection = self.selections.last().unwrap();
+        let column = self.column_from_index(filebuffer, selection.primary_index);
+
+        if textbuffer_context.horizontal_gap * 2 >= self.column_count {
+            self.horizontal_scroll = subtract_or_zero(column, self.column_count / 2);
+        } else if column < self.horizontal_scroll + textbuffer_context.horizontal_gap {
+            self.horizontal_scroll = subtract_or_zero(column, textbuffer_context.horizontal_gap);
+        } else if column + textbuffer_context.horizontal_gap + 1 > self.horizontal_scroll + self.column_count {
+            self.horizontal_scroll = column + textbuffer_context.horizontal_gap + 1 - self.column_count;
+        }
+    }
+
     fn check_bottom_scroll(&mut self, textbuffer_context: &TextbufferContext, filebuffer: &Filebuffer) {
-        let last_line = self.line_number_from_index(filebuffer, filebuffer.last_buffer_index());
+        let last_line = self.row_from_index(filebuffer, filebuffer.last_buffer_index());
         let scroll_gap = textbuffer_context.selection_gap + 1;
 
         if last_line + scroll_gap < self.line_count {
@@ -154,7 +292,7 @@ impl Textbuffer {
         }
 
         let selection = self.selections.last().unwrap();
-        let line_number = self.line_number_from_index(filebuffer, selection.primary_index);
+        let line_number = self.row_from_index(filebuffer, selection.primary_index);
 
         if textbuffer_context.selection_gap * 2 >= self.line_count {
             self.vertical_scroll = subtract_or_zero(line_number, self.line_count / 2);
@@ -168,6 +306,7 @@ impl Textbuffer {
         }
 
         self.check_bottom_scroll(textbuffer_context, filebuffer);
+        self.check_horizontal_gap(textbuffer_context, filebuffer);
     }
 
     fn add_selection_(&mut self, filebuffer: &mut Filebuffer, selection: Selection) {
@@ -202,7 +341,7 @@ impl Textbuffer {
 
     fn move_selection_left(&mut self, filebuffer: &mut Filebuffer, index: usize) -> bool {
         if self.selections[index].primary_index > 0 {
-            let new_primary = self.selections[index].primary_index - 1;
+            let new_primary = filebuffer.previous_cluster(self.selections[index].primary_index);
             self.set_primary_index(filebuffer, index, new_primary);
             return true;
         }
@@ -211,7 +350,7 @@ impl Textbuffer {
 
     fn move_selection_right(&mut self, filebuffer: &mut Filebuffer, index: usize) -> bool {
         if self.selections[index].primary_index < filebuffer.last_buffer_index() {
-            let new_primary = self.selections[index].primary_index + 1;
+            let new_primary = filebuffer.next_cluster(self.selections[index].primary_index);
             self.set_primary_index(filebuffer, index, new_primary);
             return true;
         }
@@ -221,13 +360,9 @@ impl Textbuffer {
     fn move_selection_down(&mut self, filebuffer: &mut Filebuffer, index: usize) {
         let primary_index = self.selections[index].primary_index;
 
-        for current_index in primary_index..filebuffer.last_buffer_index() {
-            if filebuffer.character(current_index).is_newline() {
-                let line_length = self.line_length_from_index(filebuffer, current_index + 1);
-                let distance_to_offset = min(line_length, self.selections[index].offset + 1);
-                self.set_primary_index(filebuffer, index, current_index + distance_to_offset);
-                return;
-            }
+        if let Some(new_primary) = self.lower_row_index(filebuffer, primary_index, self.selections[index].offset) {
+            self.set_primary_index(filebuffer, index, new_primary);
+            return;
         }
 
         if primary_index != filebuffer.last_buffer_index() {
@@ -238,13 +373,9 @@ impl Textbuffer {
     fn move_selection_up(&mut self, filebuffer: &mut Filebuffer, index: usize) {
         let primary_index = self.selections[index].primary_index;
 
-        for current_index in (0..primary_index).rev() {
-            if filebuffer.character(current_index).is_newline() {
-                let line_length = self.reverse_line_length_from_index(filebuffer, current_index) - 1;
-                let distance_to_offset = line_length - min(line_length, self.selections[index].offset);
-                self.set_primary_index(filebuffer, index, current_index - distance_to_offset);
-                return;
-            }
+        if let Some(new_primary) = self.higher_row_index(filebuffer, primary_index, self.selections[index].offset) {
+            self.set_primary_index(filebuffer, index, new_primary);
+            return;
         }
 
         if primary_index != 0 {
@@ -255,12 +386,8 @@ impl Textbuffer {
     fn lower_word(&mut self, filebuffer: &mut Filebuffer, index: usize) -> Word {
         let primary_index = self.selections[index].primary_index;
 
-        for current_index in primary_index..filebuffer.last_buffer_index() {
-            if filebuffer.character(current_index).is_newline() {
-                let line_length = self.line_length_from_index(filebuffer, current_index + 1);
-                let distance_to_offset = min(line_length, self.selections[index].offset + 1);
-                return filebuffer.word_from_index(current_index + distance_to_offset);
-            }
+        if let Some(word_index) = self.lower_row_index(filebuffer, primary_index, self.selections[index].offset) {
+            return filebuffer.word_from_index(word_index);
         }
 
         return filebuffer.last_word();
@@ -269,12 +396,8 @@ impl Textbuffer {
     fn higher_word(&mut self, filebuffer: &mut Filebuffer, index: usize) -> Word {
         let primary_index = self.selections[index].primary_index;
 
-        for current_index in (0..primary_index).rev() {
-            if filebuffer.character(current_index).is_newline() {
-                let line_length = self.reverse_line_length_from_index(filebuffer, current_index) - 1;
-                let distance_to_offset = line_length - min(line_length, self.selections[index].offset);
-                return filebuffer.word_from_index(current_index - distance_to_offset);
-            }
+        if let Some(word_index) = self.higher_row_index(filebuffer, primary_index, self.selections[index].offset) {
+            return filebuffer.word_from_index(word_index);
         }
 
         return filebuffer.first_word();
@@ -366,7 +489,7 @@ impl Textbuffer {
 
     fn set_offset(&mut self, filebuffer: &mut Filebuffer, index: usize, offset_index: usize) {
         let previous = self.selections[index].offset;
-        let new_offset = self.offset_from_index(filebuffer, offset_index);
+        let new_offset = self.column_from_index(filebuffer, offset_index);
         if previous != new_offset {
             self.selections[index].offset = new_offset;
             filebuffer.change_offset(self.window_id, index, previous, new_offset, true);
@@ -538,6 +661,22 @@ impl Textbuffer {
         //self.merge_overlapping_selections();
     }
 
+    fn insert_indented_line(&mut self, filebuffer: &mut Filebuffer, index: usize, line_index: usize, newline_index: usize) {
+        let (indentation_end, _width) = self.indentation_end(filebuffer, line_index);
+        let mut text = match indentation_end > line_index {
+            true => filebuffer.get_text().slice(line_index, indentation_end - 1),
+            false => SharedString::new(),
+        };
+
+        let indentation_length = text.len();
+        text.push(Character::from_char('\n'));
+
+        self.replace_range(filebuffer, newline_index, 0, text);
+        self.set_primary_index(filebuffer, index, newline_index + indentation_length);
+        self.update_offset(filebuffer, index);
+        self.reset_selection(filebuffer, index);
+    }
+
     fn newline_up(&mut self, textbuffer_context: &TextbufferContext, language_manager: &mut LanguageManager, filebuffer: &mut Filebuffer) {
         for index in self.selection_start()..self.selections.len() {
             self.move_selection_to_first(filebuffer, index);
@@ -550,8 +689,8 @@ impl Textbuffer {
         //self.merge_overlapping_selections();
 
         for index in self.selection_start()..self.selections.len() {
-            self.insert_text(filebuffer, self.selections[index].primary_index, SharedString::from("\n"));
-            self.advance_selections(filebuffer, index, 1);
+            let line_index = self.selections[index].primary_index;
+            self.insert_indented_line(filebuffer, index, line_index, line_index);
         }
 
         filebuffer.retokenize(language_manager);
@@ -567,12 +706,9 @@ impl Textbuffer {
         //self.merge_overlapping_selections();
 
         for index in self.selection_start()..self.selections.len() {
-            let newline_index = self.selections[index].primary_index + 1;
-            self.insert_text(filebuffer, newline_index, SharedString::from("\n"));
-            self.advance_selections(filebuffer, index, 1);
-            self.move_selection_right(filebuffer, index);
-            self.update_offset(filebuffer, index);
-            self.reset_selection(filebuffer, index);
+            let primary_index = self.selections[index].primary_index;
+            let line_index = primary_index + 1 - self.reverse_line_length_from_index(filebuffer, primary_index);
+            self.insert_indented_line(filebuffer, index, line_index, primary_index + 1);
         }
 
         self.check_selection_gaps(textbuffer_context, filebuffer);
@@ -586,6 +722,10 @@ impl Textbuffer {
                 for index in self.selection_start()..self.selections.len() {
                     if self.is_selection_extended(index) {
                         self.delete_selected(filebuffer, index);
+                    } else if textbuffer_context.auto_close && self.is_inside_empty_pair(filebuffer, self.selections[index].primary_index) {
+                        let primary_index = self.selections[index].primary_index;
+                        self.replace_range(filebuffer, primary_index - 1, 2, SharedString::new());
+                        self.update_offset(filebuffer, index);
                     } else if self.move_selection_left(filebuffer, index) {
                         self.delete_selected_primary(filebuffer, index);
                     }
@@ -914,6 +1054,31 @@ impl Textbuffer {
         //self.merge_overlapping_selections();
     }
 
+    fn page_up(&mut self, textbuffer_context: &TextbufferContext, filebuffer: &mut Filebuffer, extend: bool) {
+        let page_size = max(self.line_count, 1);
+        self.vertical_scroll = subtract_or_zero(self.vertical_scroll, page_size);
+
+        for _row in 0..page_size {
+            match extend {
+                true => self.extend_up(textbuffer_context, filebuffer),
+                false => self.move_up(textbuffer_context, filebuffer),
+            }
+        }
+    }
+
+    fn page_down(&mut self, textbuffer_context: &TextbufferContext, filebuffer: &mut Filebuffer, extend: bool) {
+        let page_size = max(self.line_count, 1);
+        self.vertical_scroll += page_size;
+        self.check_bottom_scroll(textbuffer_context, filebuffer);
+
+        for _row in 0..page_size {
+            match extend {
+                true => self.extend_down(textbuffer_context, filebuffer),
+                false => self.move_down(textbuffer_context, filebuffer),
+            }
+        }
+    }
+
     fn extend_up(&mut self, textbuffer_context: &TextbufferContext, filebuffer: &mut Filebuffer) {
         match self.mode {
 
@@ -1127,16 +1292,203 @@ impl Textbuffer {
     }
 
     fn offset_from_index(&self, filebuffer: &Filebuffer, index: usize) -> usize {
-        let mut left_offset = 0;
+        let line_index = index + 1 - self.reverse_line_length_from_index(filebuffer, index);
+        return self.column_after(filebuffer, line_index, index, 0);
+    }
 
-        for current_index in (0..index).rev() {
-            match filebuffer.character(current_index).is_newline() {
-                true => return left_offset,
-                false => left_offset += 1,
+    fn tab_width(&self, filebuffer: &Filebuffer) -> usize {
+        match self.tab_width_adjusted {
+            true => return max(self.tab_width, 1),
+            false => return max(filebuffer.get_tab_width().unwrap_or(self.tab_width), 1),
+        }
+    }
+
+    fn column_width(&self, filebuffer: &Filebuffer, index: usize, column: usize) -> usize {
+        match filebuffer.character(index) == Character::from_char('\t') {
+            true => {
+                let tab_width = self.tab_width(filebuffer);
+                return tab_width - column % tab_width;
+            },
+            false => return character_width(filebuffer.character(index)),
+        }
+    }
+
+    fn column_after(&self, filebuffer: &Filebuffer, start: usize, end: usize, column: usize) -> usize {
+        return (start..end).fold(column, |column, index| column + self.column_width(filebuffer, index, column));
+    }
+
+    fn line_rows(&self, filebuffer: &Filebuffer, line_index: usize) -> Vec<VisualRow> {
+        let line_end = line_index + self.line_length_from_index(filebuffer, line_index) - 1;
+
+        let wrap_columns = match self.wrap_columns {
+            Some(wrap_columns) if wrap_columns > 0 => wrap_columns,
+            _other => return vec![VisualRow::new(line_index, line_end, 0)],
+        };
+
+        let mut indent = 0;
+        for current_index in line_index..line_end {
+            match filebuffer.character(current_index).is_whitespace() {
+                true => indent += self.column_width(filebuffer, current_index, indent),
+                false => break,
+            }
+        }
+
+        if indent * 2 >= wrap_columns {
+            indent = 0;
+        }
+
+        let mut row_starts = vec![line_index];
+        let mut row_width = 0;
+        let mut last_boundary = None;
+        let mut current_index = line_index;
+
+        while current_index < line_end {
+            let row_start = row_starts[row_starts.len() - 1];
+            let width = self.column_width(filebuffer, current_index, row_width);
+
+            if current_index > row_start && (filebuffer.is_word_start(current_index) || filebuffer.character(current_index - 1).is_whitespace()) {
+                last_boundary = Some(current_index);
+            }
+
+            if current_index > row_start && row_width + width > wrap_columns {
+                let break_index = last_boundary.take().unwrap_or(current_index);
+                row_width = self.column_after(filebuffer, break_index, current_index, indent);
+                row_starts.push(break_index);
+                continue;
+            }
+
+            row_width += width;
+            current_index += 1;
+        }
+
+        let mut rows = Vec::new();
+
+        for (position, row_start) in row_starts.iter().enumerate() {
+            let row_end = match row_starts.get(position + 1) {
+                Some(next_start) => next_start - 1,
+                None => line_end,
+            };
+
+            let row_indent = match position {
+                0 => 0,
+                _other => indent,
+            };
+
+            rows.push(VisualRow::new(*row_start, row_end, row_indent));
+        }
+
+        return rows;
+    }
+
+    fn rows_at_index(&self, filebuffer: &Filebuffer, index: usize) -> (Vec<VisualRow>, usize) {
+        let line_index = index + 1 - self.reverse_line_length_from_index(filebuffer, index);
+        let rows = self.line_rows(filebuffer, line_index);
+        let position = rows.iter().rposition(|row| row.index <= index).unwrap_or(0);
+        return (rows, position);
+    }
+
+    fn row_cache(&self, filebuffer: &Filebuffer) -> Ref<RowCache> {
+        let key = (filebuffer.get_revision(), self.wrap_columns.unwrap_or(0), self.tab_width(filebuffer));
+
+        if self.row_cache.borrow().key != Some(key) {
+            let mut row_cache = RowCache::new();
+            let mut line_index = 0;
+
+            loop {
+                let rows = self.line_rows(filebuffer, line_index);
+                let line_end = rows[rows.len() - 1].end;
+
+                row_cache.line_starts.push(line_index);
+                row_cache.row_starts.push(row_cache.row_count);
+                row_cache.row_count += rows.len();
+
+                if line_end >= filebuffer.last_buffer_index() {
+                    break;
+                }
+
+                line_index = line_end + 1;
+            }
+
+            row_cache.key = Some(key);
+            *self.row_cache.borrow_mut() = row_cache;
+        }
+
+        return self.row_cache.borrow();
+    }
+
+    fn row_from_index(&self, filebuffer: &Filebuffer, index: usize) -> usize {
+        if self.wrap_columns.is_none() {
+            return self.line_number_from_index(filebuffer, index);
+        }
+
+        let row_cache = self.row_cache(filebuffer);
+        let line = row_cache.line_at_index(index);
+        let rows = self.line_rows(filebuffer, row_cache.line_starts[line]);
+        return row_cache.row_starts[line] + rows.iter().rposition(|row| row.index <= index).unwrap_or(0);
+    }
+
+    fn line_from_row(&self, filebuffer: &Filebuffer, row: usize) -> (usize, usize) {
+        if self.wrap_columns.is_none() {
+            return (self.index_from_line(filebuffer, row), 0);
+        }
+
+        let row_cache = self.row_cache(filebuffer);
+        let line = row_cache.line_at_row(row);
+        return (row_cache.line_starts[line], min(row - row_cache.row_starts[line], row_cache.rows_in_line(line) - 1));
+    }
+
+    fn column_from_index(&self, filebuffer: &Filebuffer, index: usize) -> usize {
+        let (rows, position) = self.rows_at_index(filebuffer, index);
+        let row = rows[position];
+        return self.column_after(filebuffer, row.index, index, row.indent);
+    }
+
+    fn index_from_column(&self, filebuffer: &Filebuffer, row: &VisualRow, column: usize) -> usize {
+        let mut left_offset = row.indent;
+
+        for current_index in row.index..row.end {
+            let width = self.column_width(filebuffer, current_index, left_offset);
+
+            if width != 0 && left_offset + width > column {
+                return current_index;
             }
+
+            left_offset += width;
+        }
+
+        return row.end;
+    }
+
+    fn lower_row_index(&self, filebuffer: &Filebuffer, index: usize, column: usize) -> Option<usize> {
+        let (rows, position) = self.rows_at_index(filebuffer, index);
+
+        if position + 1 < rows.len() {
+            return Some(self.index_from_column(filebuffer, &rows[position + 1], column));
+        }
+
+        let line_end = rows[position].end;
+        if line_end >= filebuffer.last_buffer_index() {
+            return None;
+        }
+
+        let lower_rows = self.line_rows(filebuffer, line_end + 1);
+        return Some(self.index_from_column(filebuffer, &lower_rows[0], column));
+    }
+
+    fn higher_row_index(&self, filebuffer: &Filebuffer, index: usize, column: usize) -> Option<usize> {
+        let (rows, position) = self.rows_at_index(filebuffer, index);
+
+        if position > 0 {
+            return Some(self.index_from_column(filebuffer, &rows[position - 1], column));
         }
 
-        return left_offset;
+        let line_index = rows[0].index;
+        if line_index == 0 {
+            return None;
+        }
+
+        let higher_rows = self.line_rows(filebuffer, line_index - self.reverse_line_length_from_index(filebuffer, line_index - 1));
+        return Some(self.index_from_column(filebuffer, &higher_rows[higher_rows.len() - 1], column));
     }
 
     fn line_length_from_index(&self, filebuffer: &Filebuffer, index: usize) -> usize {
@@ -1221,7 +1573,7 @@ impl Textbuffer {
 
             SelectionMode::Character => {
                 let buffer_index = self.selection_biggest_index(self.selections.len() - 1) + 1;
-                let offset = self.offset_from_index(filebuffer, buffer_index);
+                let offset = self.column_from_index(filebuffer, buffer_index);
                 let new_selection = Selection::new(buffer_index, buffer_index, offset);
                 self.add_selection_(filebuffer, new_selection);
                 self.adding_selection = true;
@@ -1232,7 +1584,7 @@ impl Textbuffer {
 
             SelectionMode::Line => {
                 let buffer_index = self.selection_biggest_index(self.selections.len() - 1) + 1;
-                let offset = self.offset_from_index(filebuffer, buffer_index);
+                let offset = self.column_from_index(filebuffer, buffer_index);
                 let new_selection = Selection::new(buffer_index, buffer_index, offset);
                 self.add_selection_(filebuffer, new_selection);
                 self.adding_selection = true;
@@ -1284,7 +1636,7 @@ impl Textbuffer {
                     let secondary_index = primary_index + selection_length - 1;
 
                     if !self.index_has_selection(primary_index, secondary_index) {
-                        let offset = self.offset_from_index(filebuffer, primary_index);
+                        let offset = self.column_from_index(filebuffer, primary_index);
                         let selection = Selection::new(primary_index, secondary_index, offset);
                         self.add_selection_(filebuffer, selection);
                     }
@@ -1456,6 +1808,11 @@ impl Textbuffer {
         self.reset_selection(filebuffer, 0);
     }
 
+    pub fn jump_to_line(&mut self, textbuffer_context: &TextbufferContext, filebuffer: &mut Filebuffer, line: usize) {
+        let index = self.index_from_line(filebuffer, line);
+        self.jump_to_index(textbuffer_context, filebuffer, index);
+    }
+
     pub fn jump_to_index(&mut self, textbuffer_context: &TextbufferContext, filebuffer: &mut Filebuffer, jump_index: usize) {
         for _index in 0..self.selections.len() - 1 {
             self.remove_selection(filebuffer, 1);
@@ -1469,6 +1826,113 @@ impl Textbuffer {
         self.check_selection_gaps(textbuffer_context, filebuffer);
     }
 
+    fn index_from_position(&self, interface_context: &InterfaceContext, textbuffer_context: &TextbufferContext, theme: &TextbufferTheme, filebuffer: &Filebuffer, position: Vector2f) -> usize {
+        let character_scaling = interface_context.character_spacing * interface_context.font_size as f32;
+        let line_scaling = interface_context.line_spacing * interface_context.font_size as f32;
+        let line_number_offset = match textbuffer_context.line_numbers {
+            true => theme.line_number_width as f32 * character_scaling + theme.line_number_offset * interface_context.font_size as f32,
+            false => 0.0,
+        };
+
+        let relative_position = position - self.position;
+        let top_offset = relative_position.y - theme.offset.y * interface_context.font_size as f32;
+        let left_offset = relative_position.x - line_number_offset - theme.offset.x * interface_context.font_size as f32;
+
+        let row = self.vertical_scroll + (top_offset.max(0.0) / line_scaling) as usize;
+        let column = self.horizontal_scroll + (left_offset.max(0.0) / character_scaling) as usize;
+
+        let (line_index, position) = self.line_from_row(filebuffer, row);
+        let rows = self.line_rows(filebuffer, line_index);
+        return self.index_from_column(filebuffer, &rows[min(position, rows.len() - 1)], column);
+    }
+
+    fn expand_selection(&mut self, textbuffer_context: &TextbufferContext, filebuffer: &mut Filebuffer, index: usize) {
+        match self.mode {
+
+            SelectionMode::Character => { },
+
+            SelectionMode::Word => {
+                self.move_secondary_to_start_of_word(filebuffer, index);
+                self.move_selection_to_end_of_word(filebuffer, index);
+            },
+
+            SelectionMode::Line => {
+                self.move_secondary_to_start(textbuffer_context, filebuffer, true, index);
+                self.move_selection_to_end(filebuffer, index);
+            },
+        }
+    }
+
+    pub fn press_mouse(&mut self, interface_context: &InterfaceContext, textbuffer_context: &TextbufferContext, theme: &TextbufferTheme, filebuffer: &mut Filebuffer, position: Vector2f, click_count: usize, add_selection: bool) {
+        let buffer_index = self.index_from_position(interface_context, textbuffer_context, theme, filebuffer, position);
+
+        if add_selection {
+            let offset = self.column_from_index(filebuffer, buffer_index);
+            self.add_selection_(filebuffer, Selection::new(buffer_index, buffer_index, offset));
+        } else {
+            for _index in 0..self.selections.len() - 1 {
+                self.remove_selection(filebuffer, 1);
+            }
+
+            let mode = match click_count {
+                1 => SelectionMode::Character,
+                2 => SelectionMode::Word,
+                _other => SelectionMode::Line,
+            };
+
+            if mode != self.mode {
+                self.set_selection_mode(filebuffer, mode);
+            }
+
+            self.set_primary_index(filebuffer, 0, buffer_index);
+            self.reset_selection(filebuffer, 0);
+        }
+
+        let index = self.selections.len() - 1;
+        self.adding_selection = false;
+        self.expand_selection(textbuffer_context, filebuffer, index);
+        self.update_offset(filebuffer, index);
+        self.drag_anchor = Some((self.selection_smallest_index(index), self.selection_biggest_index(index)));
+    }
+
+    pub fn drag_mouse(&mut self, interface_context: &InterfaceContext, textbuffer_context: &TextbufferContext, theme: &TextbufferTheme, filebuffer: &mut Filebuffer, position: Vector2f) -> bool {
+        let (anchor_start, anchor_end) = match self.drag_anchor {
+            Some(anchor) => anchor,
+            None => return false,
+        };
+
+        let buffer_index = self.index_from_position(interface_context, textbuffer_context, theme, filebuffer, position);
+        let index = self.selections.len() - 1;
+
+        if buffer_index < anchor_start {
+            self.set_secondary_index(filebuffer, index, anchor_end);
+            self.set_primary_index(filebuffer, index, buffer_index);
+
+            match self.mode {
+                SelectionMode::Character => { },
+                SelectionMode::Word => self.move_selection_to_start_of_word(filebuffer, index),
+                SelectionMode::Line => self.move_selection_to_start(textbuffer_context, filebuffer, true, index),
+            }
+        } else {
+            self.set_secondary_index(filebuffer, index, anchor_start);
+            self.set_primary_index(filebuffer, index, max(buffer_index, anchor_end));
+
+            match self.mode {
+                SelectionMode::Character => { },
+                SelectionMode::Word => self.move_selection_to_end_of_word(filebuffer, index),
+                SelectionMode::Line => self.move_selection_to_end(filebuffer, index),
+            }
+        }
+
+        self.update_offset(filebuffer, index);
+        self.check_selection_gaps(textbuffer_context, filebuffer);
+        return true;
+    }
+
+    pub fn release_mouse(&mut self) {
+        self.drag_anchor = None;
+    }
+
     pub fn handle_action(&mut self, textbuffer_context: &TextbufferContext, language_manager: &mut LanguageManager, filebuffer: &mut Filebuffer, action: Action) -> Option<Action> {
         match action {
 
@@ -1488,6 +1952,18 @@ impl Textbuffer {
 
             Action::ExtendDown => handle_return!(self.extend_down(textbuffer_context, filebuffer)),
 
+            Action::JumpToMatchingBracket => handle_return!(self.jump_to_matching_bracket(textbuffer_context, filebuffer)),
+
+            Action::SelectInsideBrackets => handle_return!(self.select_inside_brackets(textbuffer_context, filebuffer)),
+
+            Action::PageUp => handle_return!(self.page_up(textbuffer_context, filebuffer, false)),
+
+            Action::PageDown => handle_return!(self.page_down(textbuffer_context, filebuffer, false)),
+
+            Action::ExtendPageUp => handle_return!(self.page_up(textbuffer_context, filebuffer, true)),
+
+            Action::ExtendPageDown => handle_return!(self.page_down(textbuffer_context, filebuffer, true)),
+
             Action::ExtendUp => handle_return!(self.extend_up(textbuffer_context, filebuffer)),
 
             Action::ExtendLeft => handle_return!(self.extend_left(textbuffer_context, filebuffer)),
@@ -1526,6 +2002,18 @@ impl Textbuffer {
 
             Action::Rotate => handle_return!(self.rotate_selections(language_manager, filebuffer)),
 
+            Action::TabsToSpaces => handle_return!(self.convert_indentation(textbuffer_context, language_manager, filebuffer, false)),
+
+            Action::SpacesToTabs => handle_return!(self.convert_indentation(textbuffer_context, language_manager, filebuffer, true)),
+
+            Action::ToggleComment => handle_return!(self.toggle_comment(textbuffer_context, language_manager, filebuffer)),
+
+            Action::ToggleBlockComment => handle_return!(self.toggle_block_comment(textbuffer_context, language_manager, filebuffer)),
+
+            Action::Indent => handle_return!(self.shift_indentation(textbuffer_context, language_manager, filebuffer, true)),
+
+            Action::Dedent => handle_return!(self.shift_indentation(textbuffer_context, language_manager, filebuffer, false)),
+
             Action::Undo => handle_return!(self.undo(textbuffer_context, language_manager, filebuffer)),
 
             Action::Redo => handle_return!(self.redo(textbuffer_context, language_manager, filebuffer)),
@@ -1572,38 +2060,480 @@ impl Textbuffer {
         }
     }
 
-    fn get_selected_text(&self, filebuffer: &Filebuffer, index: usize) -> SharedString {
-        let start = self.selection_smallest_index(index);
-        let end = self.selection_biggest_index(index);
-        return filebuffer.get_text().slice(start, end);
-    }
-
-    fn replace_selected_text(&mut self, filebuffer: &mut Filebuffer, index: usize, new_text: SharedString) {
-        let current_length = self.selection_length(index);
+    fn replace_range(&mut self, filebuffer: &mut Filebuffer, start: usize, length: usize, new_text: SharedString) {
+        let end = start + length;
         let new_length = new_text.len();
 
-        let current_index = self.selection_smallest_index(index);
-        let current_text = filebuffer.get_text().slice(current_index, current_index + current_length - 1);
-
-        if current_text == new_text {
-            return;
+        if length > 0 {
+            self.remove_text(filebuffer, start, length);
         }
 
-        self.remove_text(filebuffer, current_index, current_length);
-        self.insert_text(filebuffer, current_index, new_text);
-
-        if current_length == new_length {
-            return;
+        if new_length > 0 {
+            self.insert_text(filebuffer, start, new_text);
         }
 
-        match current_length > new_length {
-            true => self.unadvance_selections(filebuffer, index, current_length - new_length),
-            false => self.advance_selections(filebuffer, index, new_length - current_length),
+        for index in 0..self.selections.len() {
+            let new_primary = shift_index(self.selections[index].primary_index, start, end, new_length);
+            let new_secondary = shift_index(self.selections[index].secondary_index, start, end, new_length);
+            self.set_primary_index(filebuffer, index, new_primary);
+            self.set_secondary_index(filebuffer, index, new_secondary);
         }
     }
 
-    fn rotate_selections(&mut self, language_manager: &mut LanguageManager, filebuffer: &mut Filebuffer) {
-        if self.selections.len() > 1 {
+    fn selected_line_starts(&self, filebuffer: &Filebuffer, whole_buffer: bool) -> Vec<usize> {
+        let mut line_starts = Vec::new();
+        let mut line_index = 0;
+
+        while line_index < filebuffer.length() {
+            let line_end = line_index + self.line_length_from_index(filebuffer, line_index) - 1;
+            let selected = (0..self.selections.len()).any(|index| self.selection_smallest_index(index) <= line_end && self.selection_biggest_index(index) >= line_index);
+
+            if whole_buffer || selected {
+                line_starts.push(line_index);
+            }
+
+            line_index = line_end + 1;
+        }
+
+        return line_starts;
+    }
+
+    fn indentation_end(&self, filebuffer: &Filebuffer, line_index: usize) -> (usize, usize) {
+        let tab = Character::from_char('\t');
+        let space = Character::from_char(' ');
+        let mut indentation_end = line_index;
+        let mut width = 0;
+
+        while indentation_end < filebuffer.last_buffer_index() && (filebuffer.character(indentation_end) == tab || filebuffer.character(indentation_end) == space) {
+            width += self.column_width(filebuffer, indentation_end, width);
+            indentation_end += 1;
+        }
+
+        return (indentation_end, width);
+    }
+
+    fn indentation_text(&self, filebuffer: &Filebuffer, width: usize, use_tabs: bool) -> SharedString {
+        let tab_width = self.tab_width(filebuffer);
+        let mut indentation = SharedString::new();
+        let tab_count = match use_tabs {
+            true => width / tab_width,
+            false => 0,
+        };
+
+        (0..tab_count).for_each(|_index| indentation.push(Character::from_char('\t')));
+        (0..width - tab_count * tab_width).for_each(|_index| indentation.push(Character::from_char(' ')));
+        return indentation;
+    }
+
+    fn uses_tabs(&self, filebuffer: &Filebuffer, line_index: usize) -> bool {
+        return filebuffer.character(line_index) == Character::from_char('\t');
+    }
+
+    fn set_indentation(&mut self, filebuffer: &mut Filebuffer, line_index: usize, indentation: SharedString) {
+        let (indentation_end, _width) = self.indentation_end(filebuffer, line_index);
+
+        if indentation_end == line_index && indentation.is_empty() {
+            return;
+        }
+
+        if indentation_end > line_index && filebuffer.get_text().slice(line_index, indentation_end - 1) == indentation {
+            return;
+        }
+
+        self.replace_range(filebuffer, line_index, indentation_end - line_index, indentation);
+    }
+
+    fn convert_indentation(&mut self, textbuffer_context: &TextbufferContext, language_manager: &mut LanguageManager, filebuffer: &mut Filebuffer, use_tabs: bool) {
+        let whole_buffer = !(0..self.selections.len()).any(|index| self.is_selection_extended(index));
+
+        filebuffer.begin_group();
+
+        for line_index in self.selected_line_starts(filebuffer, whole_buffer).into_iter().rev() {
+            let (indentation_end, width) = self.indentation_end(filebuffer, line_index);
+
+            if indentation_end > line_index {
+                let indentation = self.indentation_text(filebuffer, width, use_tabs);
+                self.set_indentation(filebuffer, line_index, indentation);
+            }
+        }
+
+        filebuffer.end_group();
+
+        for index in 0..self.selections.len() {
+            self.update_offset(filebuffer, index);
+        }
+
+        self.check_selection_gaps(textbuffer_context, filebuffer);
+        filebuffer.retokenize(language_manager);
+    }
+
+    fn shift_indentation(&mut self, textbuffer_context: &TextbufferContext, language_manager: &mut LanguageManager, filebuffer: &mut Filebuffer, indent: bool) {
+        let tab_width = self.tab_width(filebuffer);
+
+        filebuffer.begin_group();
+
+        for line_index in self.selected_line_starts(filebuffer, false).into_iter().rev() {
+            let (indentation_end, width) = self.indentation_end(filebuffer, line_index);
+
+            if filebuffer.character(indentation_end).is_newline() {
+                continue;
+            }
+
+            let new_width = match indent {
+                true => width + tab_width,
+                false => subtract_or_zero(width, tab_width),
+            };
+
+            let indentation = self.indentation_text(filebuffer, new_width, self.uses_tabs(filebuffer, line_index));
+            self.set_indentation(filebuffer, line_index, indentation);
+        }
+
+        filebuffer.end_group();
+
+        for index in 0..self.selections.len() {
+            self.update_offset(filebuffer, index);
+        }
+
+        self.check_selection_gaps(textbuffer_context, filebuffer);
+        filebuffer.retokenize(language_manager);
+    }
+
+    fn jump_to_matching_bracket(&mut self, textbuffer_context: &TextbufferContext, filebuffer: &mut Filebuffer) {
+        for index in 0..self.selections.len() {
+            if let Some((_bracket_index, partner_index)) = filebuffer.matching_bracket(self.selections[index].primary_index) {
+                self.set_primary_index(filebuffer, index, partner_index);
+                self.reset_selection(filebuffer, index);
+                self.update_offset(filebuffer, index);
+            }
+        }
+
+        self.check_selection_gaps(textbuffer_context, filebuffer);
+    }
+
+    fn select_inside_brackets(&mut self, textbuffer_context: &TextbufferContext, filebuffer: &mut Filebuffer) {
+        self.character_mode(filebuffer);
+
+        for index in 0..self.selections.len() {
+            if let Some((opening_index, closing_index)) = filebuffer.enclosing_brackets(self.selections[index].primary_index) {
+                self.set_secondary_index(filebuffer, index, opening_index + 1);
+
+                match closing_index > opening_index + 1 {
+                    true => self.set_primary_index(filebuffer, index, closing_index - 1),
+                    false => {
+                        self.set_primary_index(filebuffer, index, closing_index);
+                        self.reset_selection(filebuffer, index);
+                    },
+                }
+
+                self.update_offset(filebuffer, index);
+            }
+        }
+
+        self.check_selection_gaps(textbuffer_context, filebuffer);
+    }
+
+    fn text_at(&self, filebuffer: &Filebuffer, index: usize, text: &SharedString) -> bool {
+        if text.is_empty() || index + text.len() > filebuffer.last_buffer_index() {
+            return false;
+        }
+
+        return filebuffer.get_text().slice(index, index + text.len() - 1) == *text;
+    }
+
+    fn content_end(&self, filebuffer: &Filebuffer, line_index: usize) -> usize {
+        let mut content_end = line_index + self.line_length_from_index(filebuffer, line_index) - 1;
+
+        while content_end > line_index && filebuffer.character(content_end - 1).is_whitespace() {
+            content_end -= 1;
+        }
+
+        return content_end;
+    }
+
+    fn index_at_width(&self, filebuffer: &Filebuffer, line_index: usize, width: usize) -> usize {
+        let mut index = line_index;
+        let mut column = 0;
+
+        while column < width {
+            column += self.column_width(filebuffer, index, column);
+            index += 1;
+        }
+
+        return index;
+    }
+
+    fn is_line_commented(&self, filebuffer: &Filebuffer, line_index: usize, indentation_end: usize, opening: &SharedString, closing: &Option<SharedString>) -> bool {
+        if !self.text_at(filebuffer, indentation_end, opening) {
+            return false;
+        }
+
+        match closing {
+            Some(closing) => {
+                let content_end = self.content_end(filebuffer, line_index);
+                return content_end >= indentation_end + opening.len() + closing.len() && self.text_at(filebuffer, content_end - closing.len(), closing);
+            },
+            None => return true,
+        }
+    }
+
+    fn toggle_comment(&mut self, textbuffer_context: &TextbufferContext, language_manager: &mut LanguageManager, filebuffer: &mut Filebuffer) {
+        let (opening, closing) = match (filebuffer.get_line_comment(), filebuffer.get_block_comment()) {
+            (Some(line_comment), _) => (line_comment, None),
+            (None, Some((opening, closing))) => (opening, Some(closing)),
+            (None, None) => return,
+        };
+
+        let space = Character::from_char(' ');
+        let lines: Vec<(usize, usize, usize)> = self.selected_line_starts(filebuffer, false).into_iter().filter_map(|line_index| {
+            let (indentation_end, width) = self.indentation_end(filebuffer, line_index);
+            match filebuffer.character(indentation_end).is_newline() {
+                true => return None,
+                false => return Some((line_index, indentation_end, width)),
+            }
+        }).collect();
+
+        if lines.is_empty() {
+            return;
+        }
+
+        let commented = lines.iter().all(|(line_index, indentation_end, _width)| self.is_line_commented(filebuffer, *line_index, *indentation_end, &opening, &closing));
+        let smallest_width = lines.iter().map(|(_line_index, _indentation_end, width)| *width).min().unwrap();
+
+        filebuffer.begin_group();
+
+        for (line_index, indentation_end, _width) in lines.into_iter().rev() {
+            match commented {
+
+                true => {
+                    if let Some(closing) = &closing {
+                        let content_end = self.content_end(filebuffer, line_index);
+                        let mut closing_start = content_end - closing.len();
+
+                        if closing_start > indentation_end + opening.len() && filebuffer.character(closing_start - 1) == space {
+                            closing_start -= 1;
+                        }
+
+                        self.replace_range(filebuffer, closing_start, content_end - closing_start, SharedString::new());
+                    }
+
+                    let mut opening_length = opening.len();
+
+                    if filebuffer.character(indentation_end + opening_length) == space {
+                        opening_length += 1;
+                    }
+
+                    self.replace_range(filebuffer, indentation_end, opening_length, SharedString::new());
+                },
+
+                false => {
+                    if let Some(closing) = &closing {
+                        let content_end = self.content_end(filebuffer, line_index);
+                        let mut text = SharedString::from(" ");
+                        text.push_str(closing);
+                        self.replace_range(filebuffer, content_end, 0, text);
+                    }
+
+                    let comment_index = self.index_at_width(filebuffer, line_index, smallest_width);
+                    let mut text = opening.clone();
+                    text.push(space);
+                    self.replace_range(filebuffer, comment_index, 0, text);
+                },
+            }
+        }
+
+        filebuffer.end_group();
+
+        for index in 0..self.selections.len() {
+            self.update_offset(filebuffer, index);
+        }
+
+        self.check_selection_gaps(textbuffer_context, filebuffer);
+        filebuffer.retokenize(language_manager);
+    }
+
+    fn select_range(&mut self, filebuffer: &mut Filebuffer, index: usize, start: usize, last: usize) {
+        match self.selections[index].primary_index <= self.selections[index].secondary_index {
+            true => {
+                self.set_primary_index(filebuffer, index, start);
+                self.set_secondary_index(filebuffer, index, last);
+            },
+            false => {
+                self.set_primary_index(filebuffer, index, last);
+                self.set_secondary_index(filebuffer, index, start);
+            },
+        }
+    }
+
+    fn toggle_block_comment(&mut self, textbuffer_context: &TextbufferContext, language_manager: &mut LanguageManager, filebuffer: &mut Filebuffer) {
+        let (opening, closing) = match filebuffer.get_block_comment() {
+            Some(block_comment) => block_comment,
+            None => return,
+        };
+
+        filebuffer.begin_group();
+
+        for index in 0..self.selections.len() {
+            if !self.is_selection_extended(index) {
+                continue;
+            }
+
+            let start = self.selection_smallest_index(index);
+            let end = self.selection_biggest_index(index) + 1;
+            let marker_length = opening.len() + closing.len();
+
+            match end - start > marker_length && self.text_at(filebuffer, start, &opening) && self.text_at(filebuffer, end - closing.len(), &closing) {
+
+                true => {
+                    self.replace_range(filebuffer, end - closing.len(), closing.len(), SharedString::new());
+                    self.replace_range(filebuffer, start, opening.len(), SharedString::new());
+                    self.select_range(filebuffer, index, start, end - marker_length - 1);
+                },
+
+                false => {
+                    self.replace_range(filebuffer, end, 0, closing.clone());
+                    self.replace_range(filebuffer, start, 0, opening.clone());
+                    self.select_range(filebuffer, index, start, end + marker_length - 1);
+                },
+            }
+
+            self.update_offset(filebuffer, index);
+        }
+
+        filebuffer.end_group();
+        self.check_selection_gaps(textbuffer_context, filebuffer);
+        filebuffer.retokenize(language_manager);
+    }
+
+    fn line_opens_indent(&self, filebuffer: &Filebuffer, start: usize, end: usize) -> bool {
+        match filebuffer.code_words(start, end).last() {
+            Some(word) => return filebuffer.is_indent_word(word),
+            None => return false,
+        }
+    }
+
+    fn line_closes_indent(&self, filebuffer: &Filebuffer, start: usize, end: usize) -> bool {
+        match filebuffer.code_words(start, end).first() {
+            Some(word) => return filebuffer.is_dedent_word(word),
+            None => return false,
+        }
+    }
+
+    fn previous_code_line(&self, filebuffer: &Filebuffer, line_index: usize) -> Option<usize> {
+        let mut current_index = line_index;
+
+        while current_index > 0 {
+            current_index -= self.reverse_line_length_from_index(filebuffer, current_index - 1);
+            let (indentation_end, _width) = self.indentation_end(filebuffer, current_index);
+
+            if !filebuffer.character(indentation_end).is_newline() {
+                return Some(current_index);
+            }
+        }
+
+        return None;
+    }
+
+    fn indent_new_line(&mut self, filebuffer: &mut Filebuffer, index: usize, opens: bool, closes: bool) {
+        let line_index = self.selections[index].primary_index;
+        let previous_index = line_index - self.reverse_line_length_from_index(filebuffer, line_index - 1);
+        let (_indentation_end, width) = self.indentation_end(filebuffer, previous_index);
+        let use_tabs = self.uses_tabs(filebuffer, previous_index);
+        let tab_width = self.tab_width(filebuffer);
+
+        let new_width = match (opens, closes) {
+            (true, _) => width + tab_width,
+            (false, true) => subtract_or_zero(width, tab_width),
+            (false, false) => width,
+        };
+
+        let indentation = self.indentation_text(filebuffer, new_width, use_tabs);
+        let mut text = indentation.clone();
+
+        if opens && closes {
+            text.push(Character::from_char('\n'));
+            text.push_str(&self.indentation_text(filebuffer, width, use_tabs));
+        }
+
+        if text.is_empty() {
+            return;
+        }
+
+        self.replace_range(filebuffer, line_index, 0, text);
+        self.set_primary_index(filebuffer, index, line_index + indentation.len());
+        self.reset_selection(filebuffer, index);
+        self.update_offset(filebuffer, index);
+    }
+
+    fn dedent_closing_lines(&mut self, filebuffer: &mut Filebuffer) -> bool {
+        let tab_width = self.tab_width(filebuffer);
+        let mut changed = false;
+
+        for index in (0..self.selections.len()).rev() {
+            let buffer_index = self.selections[index].primary_index;
+            let line_index = buffer_index + 1 - self.reverse_line_length_from_index(filebuffer, buffer_index);
+            let (indentation_end, width) = self.indentation_end(filebuffer, line_index);
+            let code_words = filebuffer.code_words(indentation_end, buffer_index);
+
+            if width == 0 || code_words.len() != 1 || code_words[0].index + code_words[0].length != buffer_index || !filebuffer.is_dedent_word(&code_words[0]) {
+                continue;
+            }
+
+            let target_width = match self.previous_code_line(filebuffer, line_index) {
+                Some(previous_index) => {
+                    let previous_end = previous_index + self.line_length_from_index(filebuffer, previous_index) - 1;
+                    let (_indentation_end, previous_width) = self.indentation_end(filebuffer, previous_index);
+
+                    match self.line_opens_indent(filebuffer, previous_index, previous_end) {
+                        true => previous_width,
+                        false => subtract_or_zero(previous_width, tab_width),
+                    }
+                },
+                None => 0,
+            };
+
+            if target_width < width {
+                let indentation = self.indentation_text(filebuffer, target_width, self.uses_tabs(filebuffer, line_index));
+                self.set_indentation(filebuffer, line_index, indentation);
+                self.update_offset(filebuffer, index);
+                changed = true;
+            }
+        }
+
+        return changed;
+    }
+
+    fn get_selected_text(&self, filebuffer: &Filebuffer, index: usize) -> SharedString {
+        let start = self.selection_smallest_index(index);
+        let end = self.selection_biggest_index(index);
+        return filebuffer.get_text().slice(start, end);
+    }
+
+    fn replace_selected_text(&mut self, filebuffer: &mut Filebuffer, index: usize, new_text: SharedString) {
+        let current_length = self.selection_length(index);
+        let new_length = new_text.len();
+
+        let current_index = self.selection_smallest_index(index);
+        let current_text = filebuffer.get_text().slice(current_index, current_index + current_length - 1);
+
+        if current_text == new_text {
+            return;
+        }
+
+        self.remove_text(filebuffer, current_index, current_length);
+        self.insert_text(filebuffer, current_index, new_text);
+
+        if current_length == new_length {
+            return;
+        }
+
+        match current_length > new_length {
+            true => self.unadvance_selections(filebuffer, index, current_length - new_length),
+            false => self.advance_selections(filebuffer, index, new_length - current_length),
+        }
+    }
+
+    fn rotate_selections(&mut self, language_manager: &mut LanguageManager, filebuffer: &mut Filebuffer) {
+        if self.selections.len() > 1 {
             let mut buffer = self.get_selected_text(filebuffer, self.selections.len() - 1);
 
             for index in 0..self.selections.len() {
@@ -1644,33 +2574,133 @@ impl Textbuffer {
         let length = string.len();
 
         for index in positions {
-            let offset = self.offset_from_index(filebuffer, index);
+            let offset = self.column_from_index(filebuffer, index);
             let selection = Selection::new(index, index + length, offset);
             self.selections.push(selection);
         }
     }
 
-    pub fn add_character(&mut self, textbuffer_context: &TextbufferContext, language_manager: &mut LanguageManager, filebuffer: &mut Filebuffer, character: Character) {
+    fn replace_selection_with(&mut self, textbuffer_context: &TextbufferContext, filebuffer: &mut Filebuffer, index: usize, character: Character) {
+        if textbuffer_context.preserve_lines && self.is_last_selected_newline(filebuffer, index) {
+            self.selection_exclude_last(filebuffer, index);
+        }
+
+        self.replace_selected_text(filebuffer, index, character.to_string());
+        self.move_selection_to_first(filebuffer, index);
+        self.move_selection_right(filebuffer, index);
+        self.update_offset(filebuffer, index);
+        self.reset_selection(filebuffer, index);
+    }
+
+    fn may_close_pair(&self, filebuffer: &Filebuffer, buffer_index: usize, character: Character, closing: Character) -> bool {
+        let next = filebuffer.character(buffer_index);
+
+        if !next.is_whitespace() && !filebuffer.is_closing_character(next) {
+            return false;
+        }
+
+        if character == closing && buffer_index > 0 {
+            let previous = filebuffer.character(buffer_index - 1);
+            return previous.is_whitespace() || filebuffer.closing_character(previous).is_some();
+        }
+
+        return true;
+    }
+
+    fn is_inside_empty_pair(&self, filebuffer: &Filebuffer, buffer_index: usize) -> bool {
+        if buffer_index == 0 || buffer_index >= filebuffer.last_buffer_index() {
+            return false;
+        }
+
+        return filebuffer.closing_character(filebuffer.character(buffer_index - 1)) == Some(filebuffer.character(buffer_index));
+    }
+
+    fn add_paired_character(&mut self, textbuffer_context: &TextbufferContext, filebuffer: &mut Filebuffer, character: Character) -> bool {
+        let closing = filebuffer.closing_character(character);
+        let steps_over = filebuffer.is_closing_character(character);
+
+        if !textbuffer_context.auto_close || (closing.is_none() && !steps_over) {
+            return false;
+        }
 
         for index in 0..self.selections.len() {
+            let primary_index = self.selections[index].primary_index;
+
             if self.is_selection_extended(index) {
+                match closing {
+
+                    Some(closing) => {
+                        let start = self.selection_smallest_index(index);
+                        let end = self.selection_biggest_index(index) + 1;
+                        self.replace_range(filebuffer, end, 0, closing.to_string());
+                        self.replace_range(filebuffer, start, 0, character.to_string());
+                    },
 
-                if textbuffer_context.preserve_lines && self.is_last_selected_newline(filebuffer, index) {
-                    self.selection_exclude_last(filebuffer, index);
+                    None => self.replace_selection_with(textbuffer_context, filebuffer, index, character),
                 }
+                continue;
+            }
 
-                self.replace_selected_text(filebuffer, index, character.to_string());
-                self.move_selection_to_first(filebuffer, index);
-                self.move_selection_right(filebuffer, index);
-                self.update_offset(filebuffer, index);
-                self.reset_selection(filebuffer, index);
+            if steps_over && filebuffer.character(primary_index) == character {
+                self.set_primary_index(filebuffer, index, primary_index + 1);
             } else {
-                let buffer_index = self.selections[index].primary_index;
-                self.insert_text(filebuffer, buffer_index, character.to_string());
-                self.move_selection_right(filebuffer, index);
-                self.update_offset(filebuffer, index);
-                self.reset_selection(filebuffer, index);
-                self.advance_selections(filebuffer, index, 1);
+                let mut text = character.to_string();
+
+                if let Some(closing) = closing {
+                    if self.may_close_pair(filebuffer, primary_index, character, closing) {
+                        text.push(closing);
+                    }
+                }
+
+                self.replace_range(filebuffer, primary_index, 0, text);
+                self.set_primary_index(filebuffer, index, primary_index + 1);
+            }
+
+            self.update_offset(filebuffer, index);
+            self.reset_selection(filebuffer, index);
+        }
+
+        return true;
+    }
+
+    pub fn add_character(&mut self, textbuffer_context: &TextbufferContext, language_manager: &mut LanguageManager, filebuffer: &mut Filebuffer, character: Character) {
+        let indent_lines = textbuffer_context.multiline && character.is_newline();
+
+        let block_edges: Vec<(bool, bool)> = (0..self.selections.len()).map(|index| {
+            if !indent_lines {
+                return (false, false);
+            }
+
+            let start = self.selection_smallest_index(index);
+            let end = match self.is_selection_extended(index) {
+                true => self.selection_biggest_index(index) + 1,
+                false => start,
+            };
+
+            let line_index = start + 1 - self.reverse_line_length_from_index(filebuffer, start);
+            let line_end = end + self.line_length_from_index(filebuffer, end) - 1;
+            return (self.line_opens_indent(filebuffer, line_index, start), self.line_closes_indent(filebuffer, end, line_end));
+        }).collect();
+
+        filebuffer.begin_group();
+
+        if !self.add_paired_character(textbuffer_context, filebuffer, character) {
+            for index in 0..self.selections.len() {
+                if self.is_selection_extended(index) {
+                    self.replace_selection_with(textbuffer_context, filebuffer, index, character);
+                } else {
+                    let buffer_index = self.selections[index].primary_index;
+                    self.insert_text(filebuffer, buffer_index, character.to_string());
+                    self.move_selection_right(filebuffer, index);
+                    self.update_offset(filebuffer, index);
+                    self.reset_selection(filebuffer, index);
+                    self.advance_selections(filebuffer, index, 1);
+                }
+
+                if indent_lines {
+                    let (opens, closes) = block_edges[index];
+                    self.indent_new_line(filebuffer, index, opens, closes);
+                }
             }
         }
 
@@ -1681,6 +2711,12 @@ impl Textbuffer {
         self.adding_selection = false;
         self.character_mode(filebuffer);
         filebuffer.retokenize(language_manager);
+
+        if textbuffer_context.multiline && !character.is_newline() && self.dedent_closing_lines(filebuffer) {
+            filebuffer.retokenize(language_manager);
+        }
+
+        filebuffer.end_group();
     }
 
     fn render_text(&self, framebuffer: &mut RenderTexture, interface_context: &InterfaceContext, textbuffer_context: &TextbufferContext, theme: &TextbufferTheme, filebuffer: &Filebuffer, line_info: &Vec<LineInfo>) {
@@ -1692,8 +2728,8 @@ impl Textbuffer {
             false => 0.0,
         };
 
+        let text_offset = line_number_offset + theme.offset.x * interface_context.font_size as f32;
         let mut top_offset = theme.offset.y * interface_context.font_size as f32;
-        let mut left_offset = line_number_offset + theme.offset.x * interface_context.font_size as f32;
         let mut word_index = 0;
 
         let line_number_height = line_scaling - theme.line_number_gap * 2.0 * line_scaling;
@@ -1707,7 +2743,7 @@ impl Textbuffer {
 
         for line in line_info {
 
-            if textbuffer_context.line_numbers {
+            if textbuffer_context.line_numbers && !line.continued {
                 let position = self.position + Vector2f::new(theme.line_number_offset * interface_context.font_size as f32, top_offset + theme.line_number_gap * line_scaling);
                 let theme = match line.highlighted {
                     true => &theme.highlighted_line_number_theme,
@@ -1717,11 +2753,13 @@ impl Textbuffer {
                 Textfield::render(framebuffer, interface_context, theme, &format_shared!("{}", line.number), line_number_size, position, line_number_size.y);
             }
 
-            for index in line.index..filebuffer.length() {
+            let mut column = line.indent;
+            let mut base_column = column;
 
-                if filebuffer.character(index).is_newline() || left_offset > self.size.x {
-                    left_offset = line_number_offset + theme.offset.x * interface_context.font_size as f32;
-                    top_offset += line_scaling;
+            for index in line.index..line.index + line.length {
+
+                let left_offset = text_offset + (column as f32 - self.horizontal_scroll as f32) * character_scaling;
+                if left_offset > self.size.x {
                     break;
                 }
 
@@ -1732,10 +2770,88 @@ impl Textbuffer {
                     }
                 }
 
-                let character_position = self.position + Vector2f::new(left_offset, top_offset);
-                Text::render(framebuffer, interface_context, text_theme, &filebuffer.character(index).to_string(), character_size, character_position);
-                left_offset += character_scaling;
+                let width = self.column_width(filebuffer, index, column);
+                let character_column = match width {
+                    0 => base_column,
+                    _other => column,
+                };
+
+                if character_column >= self.horizontal_scroll {
+                    let character_offset = text_offset + (character_column - self.horizontal_scroll) as f32 * character_scaling;
+                    let character_position = self.position + Vector2f::new(character_offset, top_offset);
+                    Text::render(framebuffer, interface_context, text_theme, &filebuffer.character(index).to_string(), character_size, character_position);
+                }
+
+                if width != 0 {
+                    base_column = column;
+                    column += width;
+                }
             }
+
+            top_offset += line_scaling;
+        }
+    }
+
+    fn render_clip_indicators(&self, framebuffer: &mut RenderTexture, interface_context: &InterfaceContext, textbuffer_context: &TextbufferContext, theme: &TextbufferTheme, filebuffer: &Filebuffer, line_info: &Vec<LineInfo>) {
+
+        let character_scaling = interface_context.character_spacing * interface_context.font_size as f32;
+        let line_scaling = interface_context.line_spacing * interface_context.font_size as f32;
+        let line_number_offset = match textbuffer_context.line_numbers {
+            true => theme.line_number_width as f32 * character_scaling + theme.line_number_offset * interface_context.font_size as f32,
+            false => 0.0,
+        };
+
+        let left_offset = line_number_offset + theme.offset.x * interface_context.font_size as f32;
+        let right_offset = left_offset + (self.column_count as f32 - theme.clip_width) * character_scaling;
+        let clip_size = Vector2f::new(theme.clip_width * character_scaling, line_scaling);
+        let mut top_offset = theme.offset.y * interface_context.font_size as f32;
+
+        for line in line_info {
+            let row_width = self.row_width(filebuffer, line);
+
+            if self.horizontal_scroll > 0 && row_width > 0 {
+                let position = self.position + Vector2f::new(left_offset, top_offset);
+                Field::render(framebuffer, interface_context, &theme.clip_theme, clip_size, position, line_scaling);
+            }
+
+            if row_width > self.horizontal_scroll + self.column_count {
+                let position = self.position + Vector2f::new(right_offset, top_offset);
+                Field::render(framebuffer, interface_context, &theme.clip_theme, clip_size, position, line_scaling);
+            }
+
+            top_offset += line_scaling;
+        }
+    }
+
+    fn render_matching_brackets(&self, framebuffer: &mut RenderTexture, interface_context: &InterfaceContext, textbuffer_context: &TextbufferContext, theme: &TextbufferTheme, filebuffer: &Filebuffer) {
+
+        let primary_index = self.selections[self.selections.len() - 1].primary_index;
+        let (bracket_index, partner_index) = match filebuffer.matching_bracket(primary_index) {
+            Some(brackets) => brackets,
+            None => return,
+        };
+
+        let character_scaling = interface_context.character_spacing * interface_context.font_size as f32;
+        let line_scaling = interface_context.line_spacing * interface_context.font_size as f32;
+        let line_number_offset = match textbuffer_context.line_numbers {
+            true => theme.line_number_width as f32 * character_scaling + theme.line_number_offset * interface_context.font_size as f32,
+            false => 0.0,
+        };
+        let text_offset = line_number_offset + theme.offset.x * interface_context.font_size as f32;
+
+        for index in [bracket_index, partner_index].iter() {
+            let row = self.row_from_index(filebuffer, *index);
+            let column = self.column_from_index(filebuffer, *index);
+
+            if row < self.vertical_scroll || row >= self.vertical_scroll + self.line_count || column < self.horizontal_scroll {
+                continue;
+            }
+
+            let width = max(self.column_width(filebuffer, *index, column), 1);
+            let size = Vector2f::new(width as f32 * character_scaling, line_scaling);
+            let left_offset = text_offset + (column - self.horizontal_scroll) as f32 * character_scaling;
+            let top_offset = (row - self.vertical_scroll) as f32 * line_scaling + theme.offset.y * interface_context.font_size as f32;
+            Field::render(framebuffer, interface_context, &theme.matching_bracket_theme, size, self.position + Vector2f::new(left_offset, top_offset), line_scaling);
         }
     }
 
@@ -1769,15 +2885,16 @@ impl Textbuffer {
             true => theme.line_number_width as f32 * character_scaling + theme.line_number_offset * interface_context.font_size as f32,
             false => 0.0,
         };
+        let text_offset = line_number_offset + theme.offset.x * interface_context.font_size as f32;
 
         for index in 0..self.selections.len() {
 
             let start_index = self.selection_smallest_index(index);
-            let mut current_line = self.line_number_from_index(filebuffer, start_index);
+            let (mut rows, _position) = self.rows_at_index(filebuffer, start_index);
+            let mut current_line = self.row_from_index(filebuffer, start_index);
             let mut top_offset = current_line as f32 * line_scaling + theme.offset.y * interface_context.font_size as f32;
-            let mut left_offset = self.offset_from_index(filebuffer, start_index) as f32 * character_scaling + line_number_offset + theme.offset.x * interface_context.font_size as f32;
+            let mut column = self.column_from_index(filebuffer, start_index);
             let selection_length = self.selection_length(index);
-            let selection_size = Vector2f::new(character_scaling, line_scaling);
 
             for offset in 0..selection_length {
                 if current_line >= self.vertical_scroll {
@@ -1797,29 +2914,43 @@ impl Textbuffer {
                         &selection_theme.center_selection_theme
                     };
 
-                    let character = match start_index + offset < filebuffer.length() {
-                        true => filebuffer.character(start_index + offset).to_string(),
-                        false => SharedString::from(" "),
+                    let (character, width) = match start_index + offset < filebuffer.length() {
+                        true => (filebuffer.cluster_text(start_index + offset), self.column_width(filebuffer, start_index + offset, column)),
+                        false => (SharedString::from(" "), 1),
                     };
 
-                    let position = self.position + Vector2f::new(left_offset, top_offset - scroll_offset + theme.offset.y * interface_context.font_size as f32);
-                    Textfield::render(framebuffer, interface_context, selection_theme, &character, selection_size, position, character_scaling);
+                    if (width != 0 || selection_length == 1) && column >= self.horizontal_scroll {
+                        let selection_size = Vector2f::new(max(width, 1) as f32 * character_scaling, line_scaling);
+                        let left_offset = text_offset + (column - self.horizontal_scroll) as f32 * character_scaling;
+                        let position = self.position + Vector2f::new(left_offset, top_offset - scroll_offset + theme.offset.y * interface_context.font_size as f32);
+                        Textfield::render(framebuffer, interface_context, selection_theme, &character, selection_size, position, character_scaling);
+                    }
                 } else if top_offset - scroll_offset > self.size.y {
                     break;
                 }
 
+                let next_index = start_index + offset + 1;
+
                 if filebuffer.character(start_index + offset).is_newline() {
-                    left_offset = line_number_offset + theme.offset.x * interface_context.font_size as f32;
+                    column = 0;
+                    top_offset += line_scaling;
+                    current_line += 1;
+
+                    if next_index < filebuffer.length() {
+                        rows = self.line_rows(filebuffer, next_index);
+                    }
+                } else if let Some(row) = rows.iter().skip(1).find(|row| row.index == next_index) {
+                    column = row.indent;
                     top_offset += line_scaling;
                     current_line += 1;
                 } else {
-                    left_offset += character_scaling;
+                    column += self.column_width(filebuffer, start_index + offset, column);
                 }
             }
         }
     }
 
-    fn render_status_bar(&self, framebuffer: &mut RenderTexture, interface_context: &InterfaceContext, theme: &StatusBarTheme, filebuffer: &Filebuffer) {
+    fn render_status_bar(&self, framebuffer: &mut RenderTexture, interface_context: &InterfaceContext, textbuffer_context: &TextbufferContext, theme: &StatusBarTheme, filebuffer: &Filebuffer) {
 
         //let mut status_bar_pieces = Vec::new();
         //status_bar_pieces.push(Piece::new(self.mode.name(), &theme.mode_theme));
@@ -1828,6 +2959,14 @@ impl Textbuffer {
 
         let mut status_bar_content = SharedString::new();
 
+        if let Some(status_hint) = &self.status_hint {
+            status_bar_content.push_str(&format_shared!("{}   ", status_hint));
+        }
+
+        if self.recording {
+            status_bar_content.push_str(&SharedString::from("recording   "));
+        }
+
         let primary_index = self.selections[self.selections.len() - 1].primary_index;
         let line_number = self.line_number_from_index(filebuffer, primary_index) + 1;
         let character = self.offset_from_index(filebuffer, primary_index) + 1;
@@ -1843,6 +2982,7 @@ impl Textbuffer {
             status_bar_content.push_str(&format_shared!("{}   ", error.display(&None, &map!())));
         }
 
+        status_bar_content.push_str(&format_shared!("{}   ", filebuffer.get_format().display()));
         status_bar_content.push_str(&format_shared!("{}:{}:{}   ", line_number, character, length));
         status_bar_content.push_str(&format_shared!("{}", self.mode.name()));
 
@@ -1850,6 +2990,18 @@ impl Textbuffer {
         let size = Vector2f::new(self.size.x, status_bar_height);
         let position = Vector2f::new(0.0, self.size.y - status_bar_height);
         Textfield::render(framebuffer, interface_context, &theme.textfield_theme, &status_bar_content, size, position, interface_context.font_size as f32);
+
+        if textbuffer_context.modal {
+            let mode_theme = match self.edit_mode {
+                EditMode::Normal => &theme.normal_mode_theme,
+                EditMode::Insert => &theme.insert_mode_theme,
+            };
+
+            let mode_name = SharedString::from(self.edit_mode.name());
+            let character_scaling = interface_context.character_spacing * interface_context.font_size as f32;
+            let mode_width = mode_name.len() as f32 * character_scaling + mode_theme.text_offset * 2.0 * interface_context.font_size as f32;
+            Textfield::render(framebuffer, interface_context, mode_theme, &mode_name, Vector2f::new(mode_width, status_bar_height), position, interface_context.font_size as f32);
+        }
     }
 
     pub fn render(&self, framebuffer: &mut RenderTexture, interface_context: &InterfaceContext, textbuffer_context: &TextbufferContext, theme: &TextbufferTheme, filebuffer: &Filebuffer, scaler: f32, focused: bool) {
@@ -1861,40 +3013,56 @@ impl Textbuffer {
             self.render_selection_lines(framebuffer, interface_context, textbuffer_context, theme, filebuffer, &line_info);
         }
 
+        if textbuffer_context.highlighting && focused {
+            self.render_matching_brackets(framebuffer, interface_context, textbuffer_context, theme, filebuffer);
+        }
+
         self.render_text(framebuffer, interface_context, textbuffer_context, theme, filebuffer, &line_info);
 
+        if self.column_count != 0 && self.wrap_columns.is_none() {
+            self.render_clip_indicators(framebuffer, interface_context, textbuffer_context, theme, filebuffer, &line_info);
+        }
+
         if focused || textbuffer_context.unfocused_selections {
             self.render_selections(framebuffer, interface_context, textbuffer_context, theme, filebuffer);
         }
 
         if textbuffer_context.status_bar {
-            self.render_status_bar(framebuffer, interface_context, &theme.status_bar_theme, filebuffer);
+            self.render_status_bar(framebuffer, interface_context, textbuffer_context, &theme.status_bar_theme, filebuffer);
         }
     }
 
     pub fn line_info(&self, textbuffer_context: &TextbufferContext, filebuffer: &Filebuffer) -> Vec<LineInfo> {
 
         let mut line_info = Vec::new();
-        let mut line_number = self.vertical_scroll;
-        let index = self.index_from_line(filebuffer, line_number);
-        let mut line_start = index;
-        let mut line_length = 0;
-
-        for index in index..filebuffer.length() {
+        let (mut line_index, mut position) = self.line_from_row(filebuffer, self.vertical_scroll);
+        let mut line_number = self.line_number_from_index(filebuffer, line_index);
+
+        while line_info.len() <= self.line_count {
+            let rows = self.line_rows(filebuffer, line_index);
+            let line_end = rows[rows.len() - 1].end;
+            line_number += 1;
+
+            for (row_position, row) in rows.iter().enumerate().skip(position) {
+                let wrapped = row_position + 1 < rows.len();
+                let length = match wrapped {
+                    true => row.end + 1 - row.index,
+                    false => row.end - row.index,
+                };
 
-            if line_number > self.vertical_scroll + self.line_count {
-                break;
+                let mut line = LineInfo::new(line_number, row.index, length);
+                line.indent = row.indent;
+                line.continued = row_position > 0;
+                line.wrapped = wrapped;
+                line_info.push(line);
             }
 
-            if filebuffer.character(index).is_newline() || index == filebuffer.last_buffer_index() {
-                line_number += 1;
-                line_info.push(LineInfo::new(line_number, line_start, line_length));
-                line_start = index + 1;
-                line_length = 0;
-                continue;
+            if line_end >= filebuffer.last_buffer_index() {
+                break;
             }
 
-            line_length += 1;
+            line_index = line_end + 1;
+            position = 0;
         }
 
         for index in 0..self.selections.len() {
@@ -1902,7 +3070,7 @@ impl Textbuffer {
             let biggest_index = self.selection_biggest_index(index);
 
             for line in &mut line_info {
-                if biggest_index >= line.index && smallest_index <= line.index + line.length {
+                if biggest_index >= line.index && smallest_index <= line.last_index() {
                     line.highlighted = true;
                 }
             }
@@ -0,0 +1,15 @@
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum EditMode {
+    Normal,
+    Insert,
+}
+
+impl EditMode {
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            EditMode::Normal => return "normal",
+            EditMode::Insert => return "insert",
+        }
+    }
+}
@@ -3,15 +3,20 @@ use seamonkey::*;
 use sfml::system::Vector2f;
 use sfml::graphics::RenderTexture;
 
-use themes::{ TextfieldTheme, Alignment };
+use themes::{ TextfieldTheme, TextTheme, Alignment };
 use elements::{ Text, Field };
 use interface::InterfaceContext;
+use system::text_width;
 
 pub struct Textfield { }
 
 impl Textfield {
 
     pub fn render(framebuffer: &mut RenderTexture, interface_context: &InterfaceContext, theme: &TextfieldTheme, text: &SharedString, size: Vector2f, position: Vector2f, scaler: f32) {
+        Self::render_matched(framebuffer, interface_context, theme, &theme.text_theme, text, &[], size, position, scaler);
+    }
+
+    pub fn render_matched(framebuffer: &mut RenderTexture, interface_context: &InterfaceContext, theme: &TextfieldTheme, matched_theme: &TextTheme, text: &SharedString, matched: &[usize], size: Vector2f, position: Vector2f, scaler: f32) {
 
         let text_position = match &theme.alignment {
 
@@ -22,20 +27,20 @@ impl Textfield {
 
             Alignment::Center => {
                 let character_scaling = interface_context.character_spacing * interface_context.font_size as f32;
-                let text_size = text.len() as f32 * character_scaling;
+                let text_size = text_width(text) as f32 * character_scaling;
                 let left_position = (size.x - text_size) / 2.0;
                 position + Vector2f::new(left_position, 0.0)
             }
 
             Alignment::Right => {
                 let character_scaling = interface_context.character_spacing * interface_context.font_size as f32;
-                let text_size = text.len() as f32 * character_scaling;
+                let text_size = text_width(text) as f32 * character_scaling;
                 let left_position = size.x - text_size - theme.text_offset * interface_context.font_size as f32;
                 position + Vector2f::new(left_position as f32, 0.0)
             }
         };
 
         Field::render(framebuffer, interface_context, &theme.field_theme, size, position, scaler);
-        Text::render(framebuffer, interface_context, &theme.text_theme, text, size, text_position);
+        Text::render_matched(framebuffer, interface_context, &theme.text_theme, matched_theme, text, matched, size, text_position);
     }
 }
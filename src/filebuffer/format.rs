@@ -0,0 +1,277 @@
+use seamonkey::*;
+
+use std::fs::{ read, write };
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Encoding {
+    Utf8,
+    Utf16LittleEndian,
+    Utf16BigEndian,
+}
+
+impl Encoding {
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Utf8 => return "utf-8",
+            Encoding::Utf16LittleEndian => return "utf-16le",
+            Encoding::Utf16BigEndian => return "utf-16be",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            Encoding::Utf8 => return Encoding::Utf16LittleEndian,
+            Encoding::Utf16LittleEndian => return Encoding::Utf16BigEndian,
+            Encoding::Utf16BigEndian => return Encoding::Utf8,
+        }
+    }
+
+    fn byte_order_mark(&self) -> &'static [u8] {
+        match self {
+            Encoding::Utf8 => return &[0xEF, 0xBB, 0xBF],
+            Encoding::Utf16LittleEndian => return &[0xFF, 0xFE],
+            Encoding::Utf16BigEndian => return &[0xFE, 0xFF],
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum LineEnding {
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => return "lf",
+            LineEnding::CrLf => return "crlf",
+        }
+    }
+
+    pub fn toggle(&self) -> Self {
+        match self {
+            LineEnding::Lf => return LineEnding::CrLf,
+            LineEnding::CrLf => return LineEnding::Lf,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FileFormat {
+    pub encoding: Encoding,
+    pub byte_order_mark: bool,
+    pub line_ending: LineEnding,
+    pub final_newline: bool,
+}
+
+impl FileFormat {
+
+    pub fn new() -> Self {
+        return Self {
+            encoding: Encoding::Utf8,
+            byte_order_mark: false,
+            line_ending: LineEnding::Lf,
+            final_newline: true,
+        }
+    }
+
+    pub fn display(&self) -> SharedString {
+        let mut display = SharedString::from(self.encoding.name());
+
+        if self.byte_order_mark {
+            display.push_str(&SharedString::from(" bom"));
+        }
+
+        display.push_str(&format_shared!(" {}", self.line_ending.name()));
+
+        if !self.final_newline {
+            display.push_str(&SharedString::from(" noeol"));
+        }
+
+        return display;
+    }
+
+    fn detect_encoding(bytes: &[u8]) -> (Encoding, bool) {
+        for encoding in [Encoding::Utf8, Encoding::Utf16LittleEndian, Encoding::Utf16BigEndian].iter() {
+            if bytes.starts_with(encoding.byte_order_mark()) {
+                return (*encoding, true);
+            }
+        }
+
+        if bytes.len() >= 2 && bytes.len() % 2 == 0 {
+            let even_zeros = bytes.iter().step_by(2).filter(|byte| **byte == 0).count();
+            let odd_zeros = bytes.iter().skip(1).step_by(2).filter(|byte| **byte == 0).count();
+
+            if odd_zeros > bytes.len() / 4 && even_zeros == 0 {
+                return (Encoding::Utf16LittleEndian, false);
+            }
+
+            if even_zeros > bytes.len() / 4 && odd_zeros == 0 {
+                return (Encoding::Utf16BigEndian, false);
+            }
+        }
+
+        return (Encoding::Utf8, false);
+    }
+
+    fn decode_utf16(bytes: &[u8], encoding: Encoding) -> Status<String> {
+        if bytes.len() % 2 != 0 {
+            return error!(string!("{} file has an odd number of bytes", encoding.name()));
+        }
+
+        let units: Vec<u16> = bytes.chunks(2).map(|pair| match encoding {
+            Encoding::Utf16BigEndian => u16::from_be_bytes([pair[0], pair[1]]),
+            _other => u16::from_le_bytes([pair[0], pair[1]]),
+        }).collect();
+
+        match String::from_utf16(&units) {
+            Ok(text) => return success!(text),
+            Err(_error) => return error!(string!("file is not valid {}", encoding.name())),
+        }
+    }
+
+    pub fn decode(bytes: &[u8]) -> Status<(SharedString, Self)> {
+        let (encoding, byte_order_mark) = Self::detect_encoding(bytes);
+        let bytes = match byte_order_mark {
+            true => &bytes[encoding.byte_order_mark().len()..],
+            false => bytes,
+        };
+
+        let mut text = match encoding {
+            Encoding::Utf8 => match String::from_utf8(bytes.to_vec()) {
+                Ok(text) => text,
+                Err(_error) => return error!(string!("file is not valid utf-8")),
+            },
+            _other => confirm!(Self::decode_utf16(bytes, encoding)),
+        };
+
+        let crlf_count = text.matches("\r\n").count();
+        let line_ending = match crlf_count > 0 && crlf_count * 2 >= text.matches('\n').count() {
+            true => LineEnding::CrLf,
+            false => LineEnding::Lf,
+        };
+
+        if line_ending == LineEnding::CrLf {
+            text = text.replace("\r\n", "\n");
+        }
+
+        let final_newline = text.ends_with('\n');
+        if !final_newline {
+            text.push('\n');
+        }
+
+        let format = Self {
+            encoding: encoding,
+            byte_order_mark: byte_order_mark,
+            line_ending: line_ending,
+            final_newline: final_newline,
+        };
+
+        return success!((SharedString::from(text.as_str()), format));
+    }
+
+    pub fn encode(&self, text: &SharedString) -> Vec<u8> {
+        let mut text = text.serialize();
+
+        if !self.final_newline && text.ends_with('\n') {
+            text.pop();
+        }
+
+        if self.line_ending == LineEnding::CrLf {
+            text = text.replace('\n', "\r\n");
+        }
+
+        let mut bytes = Vec::new();
+
+        if self.byte_order_mark {
+            bytes.extend_from_slice(self.encoding.byte_order_mark());
+        }
+
+        match self.encoding {
+            Encoding::Utf8 => bytes.extend_from_slice(text.as_bytes()),
+            Encoding::Utf16LittleEndian => text.encode_utf16().for_each(|unit| bytes.extend_from_slice(&unit.to_le_bytes())),
+            Encoding::Utf16BigEndian => text.encode_utf16().for_each(|unit| bytes.extend_from_slice(&unit.to_be_bytes())),
+        }
+
+        return bytes;
+    }
+}
+
+pub fn read_encoded_file(file_name: &SharedString) -> Status<(SharedString, FileFormat)> {
+    match read(file_name.serialize()) {
+        Ok(bytes) => return FileFormat::decode(&bytes),
+        Err(_error) => return error!(string!("failed to read file {}", file_name.serialize())),
+    }
+}
+
+pub fn write_encoded_file(file_name: &SharedString, text: &SharedString, format: &FileFormat) -> Status<()> {
+    match write(file_name.serialize(), format.encode(text)) {
+        Ok(()) => return success!(()),
+        Err(_error) => return error!(string!("failed to write file {}", file_name.serialize())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use seamonkey::*;
+
+    use super::{ Encoding, FileFormat, LineEnding };
+
+    fn decode(bytes: &[u8]) -> (String, FileFormat) {
+        match FileFormat::decode(bytes) {
+            Status::Success((text, format)) => return (text.serialize(), format),
+            Status::Error(_error) => panic!("failed to decode {:?}", bytes),
+        }
+    }
+
+    #[test]
+    fn decode_empty() {
+        let (text, format) = decode(&[]);
+        assert_eq!(text, "\n");
+        assert_eq!(format.encoding, Encoding::Utf8);
+        assert!(!format.byte_order_mark);
+        assert!(!format.final_newline);
+        assert!(format.encode(&SharedString::from(text.as_str())).is_empty());
+    }
+
+    #[test]
+    fn decode_crlf() {
+        let bytes = b"first\r\nsecond\r\n";
+        let (text, format) = decode(bytes);
+        assert_eq!(text, "first\nsecond\n");
+        assert_eq!(format.line_ending, LineEnding::CrLf);
+        assert!(format.final_newline);
+        assert_eq!(format.encode(&SharedString::from(text.as_str())), bytes.to_vec());
+    }
+
+    #[test]
+    fn decode_byte_order_mark() {
+        let bytes = [0xEF, 0xBB, 0xBF, b'a', b'\n'];
+        let (text, format) = decode(&bytes);
+        assert_eq!(text, "a\n");
+        assert_eq!(format.encoding, Encoding::Utf8);
+        assert!(format.byte_order_mark);
+        assert_eq!(format.encode(&SharedString::from(text.as_str())), bytes.to_vec());
+    }
+
+    #[test]
+    fn decode_utf16() {
+        let little_endian = [0xFF, 0xFE, b'a', 0, b'\n', 0];
+        let (text, format) = decode(&little_endian);
+        assert_eq!(text, "a\n");
+        assert_eq!(format.encoding, Encoding::Utf16LittleEndian);
+        assert!(format.byte_order_mark);
+        assert_eq!(format.encode(&SharedString::from(text.as_str())), little_endian.to_vec());
+
+        let big_endian = [0, b'a', 0, b'b', 0, b'\n'];
+        let (text, format) = decode(&big_endian);
+        assert_eq!(text, "ab\n");
+        assert_eq!(format.encoding, Encoding::Utf16BigEndian);
+        assert!(!format.byte_order_mark);
+        assert_eq!(format.encode(&SharedString::from(text.as_str())), big_endian.to_vec());
+    }
+}
@@ -1,5 +1,6 @@
 use seamonkey::*;
 
+use std::cmp::min;
 use std::time::{ Duration, SystemTime };
 
 use selection::{ Selection, SelectionMode };
@@ -11,6 +12,8 @@ const COMBINE_DURATION: f32 = 0.5;
 pub struct History {
     actions: Vec<BufferActionStep>,
     timestamp: SystemTime,
+    group_start: Option<usize>,
+    group_depth: usize,
 }
 
 impl History {
@@ -19,6 +22,8 @@ impl History {
         return Self {
             actions: Vec::new(),
             timestamp: SystemTime::now(),
+            group_start: None,
+            group_depth: 0,
         }
     }
 
@@ -28,8 +33,31 @@ impl History {
         return elapsed_time <= COMBINE_DURATION;
     }
 
+    pub fn begin_group(&mut self, start: usize) {
+        if self.group_depth == 0 {
+            self.group_start = Some(min(start, self.actions.len()));
+        }
+
+        self.group_depth += 1;
+    }
+
+    pub fn end_group(&mut self) {
+        if self.group_depth == 0 {
+            return;
+        }
+
+        self.group_depth -= 1;
+
+        if self.group_depth == 0 {
+            self.group_start = None;
+        }
+    }
+
     fn append_action(&mut self, action: BufferAction, combine: bool) {
-        let combined = combine && self.update_timestamp();
+        let combined = match self.group_start {
+            Some(group_start) if self.actions.len() > group_start => true,
+            _other => combine && self.update_timestamp(),
+        };
         self.actions.push(BufferActionStep::new(action, combined));
     }
 
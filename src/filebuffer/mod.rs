@@ -1,51 +1,116 @@
 mod action;
 mod history;
 mod step;
+mod format;
+mod note;
 
 use self::step::BufferActionStep;
 use self::history::History;
 
 pub use self::action::BufferAction;
+pub use self::format::{ FileFormat, Encoding, LineEnding, read_encoded_file, write_encoded_file };
+pub use self::note::BracketNote;
 
 use seamonkey::*;
 
+use std::cmp::{ min, max, Ordering };
+use std::sync::atomic::{ AtomicUsize, Ordering as AtomicOrdering };
+
 #[cfg(feature = "debug")]
 use debug::*;
 
 use themes::{ TextbufferTheme, TextTheme };
 use selection::SelectionMode;
 use elements::Word;
-use managers::LanguageManager;
+use managers::{ LanguageManager, LanguageSettings };
+use system::{ is_grapheme_extend, is_joiner };
+
+static REVISION_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+fn next_revision() -> usize {
+    return REVISION_COUNTER.fetch_add(1, AtomicOrdering::Relaxed);
+}
 
 pub fn length_from_position(position: Vec<Position>) -> usize {
     return position.iter().map(|position| position.length).sum();
 }
 
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum BracketDepth {
+    Plain,
+    Nested(usize),
+    Unbalanced,
+}
+
 #[derive(Clone)]
 pub struct Filebuffer {
     text: SharedString,
     history: History,
     history_index: usize,
+    saved_index: Option<usize>,
     words: Vec<Word>,
     language: SharedString,
-    notes: Vec<Note>
+    notes: Vec<Note>,
+    format: FileFormat,
+    saved_format: FileFormat,
+    settings: LanguageSettings,
+    bracket_partners: Vec<Option<usize>>,
+    bracket_notes: Vec<BracketNote>,
+    bracket_depths: Vec<BracketDepth>,
+    revision: usize,
 }
 
 impl Filebuffer {
 
     pub fn new(language_manager: &mut LanguageManager, language: SharedString, text: SharedString) -> Self {
         let (words, notes) = display!(Self::tokenize(language_manager, &language, &text));
+        let settings = Self::language_settings(language_manager, &language);
+        let (bracket_partners, unmatched_brackets, bracket_depths) = Self::match_brackets(&words, &settings);
+        let bracket_notes = Self::bracket_notes(&text, &words, &unmatched_brackets);
 
         return Self {
             text: text,
             history: History::new(),
             history_index: 0,
+            saved_index: Some(0),
             words: words,
             language: language,
             notes: notes,
+            format: FileFormat::new(),
+            saved_format: FileFormat::new(),
+            settings: settings,
+            bracket_partners: bracket_partners,
+            bracket_notes: bracket_notes,
+            bracket_depths: bracket_depths,
+            revision: next_revision(),
+        }
+    }
+
+    fn language_settings(language_manager: &mut LanguageManager, language: &SharedString) -> LanguageSettings {
+        match language_manager.get_settings(language) {
+            Status::Success(settings) => return settings,
+            Status::Error(_error) => return LanguageSettings::new(),
         }
     }
 
+    pub fn get_tab_width(&self) -> Option<usize> {
+        return self.settings.tab_width;
+    }
+
+    pub fn with_format(mut self, format: FileFormat) -> Self {
+        self.format = format;
+        self.saved_format = format;
+        return self;
+    }
+
+    pub fn get_format(&self) -> FileFormat {
+        return self.format;
+    }
+
+    pub fn set_format(&mut self, format: FileFormat) {
+        self.format = format;
+    }
+
     fn tokenize(language_manager: &mut LanguageManager, language: &SharedString, text: &SharedString) -> Status<(Vec<Word>, Vec<Note>)> {
 
         #[cfg(feature = "debug")]
@@ -67,23 +132,115 @@ impl Filebuffer {
         return success!((words, notes));
     }
 
+    fn bracket_kind(settings: &LanguageSettings, word: &Word) -> Option<(usize, bool)> {
+        if let TokenType::Operator(operator) = &word.token_type {
+            for (kind, (opening, closing)) in settings.bracket_operators.iter().enumerate() {
+                if operator == opening {
+                    return Some((kind, true));
+                }
+
+                if operator == closing {
+                    return Some((kind, false));
+                }
+            }
+        }
+
+        return None;
+    }
+
+    fn match_brackets(words: &Vec<Word>, settings: &LanguageSettings) -> (Vec<Option<usize>>, Vec<usize>, Vec<BracketDepth>) {
+        let mut bracket_partners = vec![None; words.len()];
+        let mut bracket_depths = vec![BracketDepth::Plain; words.len()];
+        let mut unmatched_brackets = Vec::new();
+        let mut open_brackets: Vec<(usize, usize)> = Vec::new();
+
+        for (word_index, word) in words.iter().enumerate() {
+            match Self::bracket_kind(settings, word) {
+
+                Some((kind, true)) => {
+                    bracket_depths[word_index] = BracketDepth::Nested(open_brackets.len());
+                    open_brackets.push((word_index, kind));
+                },
+
+                Some((kind, false)) => match open_brackets.last() {
+                    Some((open_index, open_kind)) if *open_kind == kind => {
+                        bracket_partners[*open_index] = Some(word_index);
+                        bracket_partners[word_index] = Some(*open_index);
+                        bracket_depths[word_index] = bracket_depths[*open_index];
+                        open_brackets.pop();
+                    },
+                    _other => {
+                        bracket_depths[word_index] = BracketDepth::Unbalanced;
+                        unmatched_brackets.push(word_index);
+                    },
+                },
+
+                None => { },
+            }
+        }
+
+        unmatched_brackets.extend(open_brackets.into_iter().map(|(word_index, _kind)| word_index));
+        unmatched_brackets.sort();
+        return (bracket_partners, unmatched_brackets, bracket_depths);
+    }
+
+    fn bracket_notes(text: &SharedString, words: &Vec<Word>, unmatched_brackets: &Vec<usize>) -> Vec<BracketNote> {
+        let mut bracket_notes = Vec::new();
+        let mut line = 1;
+        let mut current_index = 0;
+
+        for word_index in unmatched_brackets.iter() {
+            let word = &words[*word_index];
+
+            while current_index < word.index {
+                if text[current_index].is_newline() {
+                    line += 1;
+                }
+                current_index += 1;
+            }
+
+            let bracket = text.slice(word.index, word.index + word.length - 1);
+            bracket_notes.push(BracketNote::new(line, word.index, bracket));
+        }
+
+        return bracket_notes;
+    }
+
     pub fn retokenize(&mut self, language_manager: &mut LanguageManager) -> Status<()> {
         let (words, notes) = confirm!(Self::tokenize(language_manager, &self.language, &self.text));
+        let (bracket_partners, unmatched_brackets, bracket_depths) = Self::match_brackets(&words, &self.settings);
+        self.bracket_notes = Self::bracket_notes(&self.text, &words, &unmatched_brackets);
         self.words = words;
         self.notes = notes;
+        self.bracket_partners = bracket_partners;
+        self.bracket_depths = bracket_depths;
+        self.revision = next_revision();
         return success!(());
     }
 
+    pub fn get_revision(&self) -> usize {
+        return self.revision;
+    }
+
+    pub fn get_language(&self) -> SharedString {
+        return self.language.clone();
+    }
+
     pub fn get_notes(&self) -> Vec<Note> {
         return self.notes.clone();
     }
 
+    pub fn get_bracket_notes(&self) -> Vec<BracketNote> {
+        return self.bracket_notes.clone();
+    }
+
     pub fn set_language(&mut self, language_manager: &mut LanguageManager, language: SharedString) -> Status<()> {
         if self.language == language {
             return success!(());
         }
 
         self.language = language;
+        self.settings = Self::language_settings(language_manager, &self.language);
         return self.retokenize(language_manager);
     }
 
@@ -95,12 +252,20 @@ impl Filebuffer {
         return self.words.last().unwrap().clone();
     }
 
+    pub fn is_word_start(&self, index: usize) -> bool {
+        return self.words.binary_search_by_key(&index, |word| word.index).is_ok();
+    }
+
     pub fn word_last_index(&self, word_index: usize) -> usize {
         return self.words[word_index].index + self.words[word_index].length;
     }
 
     pub fn word_theme<'t>(&self, theme: &'t TextbufferTheme, word_index: usize) -> &'t TextTheme {
-        return self.words[word_index].get_theme(theme);
+        match self.bracket_depths[word_index] {
+            BracketDepth::Nested(depth) if !theme.bracket_themes.is_empty() => return &theme.bracket_themes[depth % theme.bracket_themes.len()],
+            BracketDepth::Unbalanced => return &theme.invalid_theme,
+            _other => return self.words[word_index].get_theme(theme),
+        }
     }
 
     pub fn left_word(&self, index: usize) -> Word {
@@ -136,6 +301,105 @@ impl Filebuffer {
         panic!("word from index failed; index {}; length {}", index, self.text.len());
     }
 
+    pub fn code_words(&self, start: usize, end: usize) -> Vec<Word> {
+        return self.words.iter()
+            .filter(|word| word.index >= start && word.index + word.length <= end)
+            .filter(|word| match word.token_type {
+                TokenType::Ignored => false,
+                TokenType::Comment(..) => false,
+                _other => true,
+            })
+            .cloned()
+            .collect();
+    }
+
+    pub fn is_indent_word(&self, word: &Word) -> bool {
+        match &word.token_type {
+            TokenType::Operator(operator) => return self.settings.indent_operators.contains(operator),
+            _other => return false,
+        }
+    }
+
+    pub fn is_dedent_word(&self, word: &Word) -> bool {
+        match &word.token_type {
+            TokenType::Operator(operator) => return self.settings.dedent_operators.contains(operator),
+            _other => return false,
+        }
+    }
+
+    pub fn closing_character(&self, character: Character) -> Option<Character> {
+        return self.settings.pairs.iter().find(|(opening, _closing)| *opening == character).map(|(_opening, closing)| *closing);
+    }
+
+    pub fn is_closing_character(&self, character: Character) -> bool {
+        return self.settings.pairs.iter().any(|(_opening, closing)| *closing == character);
+    }
+
+    pub fn get_line_comment(&self) -> Option<SharedString> {
+        return self.settings.line_comment.clone();
+    }
+
+    pub fn get_block_comment(&self) -> Option<(SharedString, SharedString)> {
+        return self.settings.block_comment.clone();
+    }
+
+    fn word_index_at(&self, index: usize) -> Option<usize> {
+        match self.words.binary_search_by(|word| match (word.index > index, word.index + word.length <= index) {
+            (true, _) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => Ordering::Equal,
+        }) {
+            Ok(word_index) => return Some(word_index),
+            Err(_word_index) => return None,
+        }
+    }
+
+    fn bracket_at(&self, index: usize) -> Option<(usize, usize)> {
+        if let Some(word_index) = self.word_index_at(index) {
+            if let Some(partner_index) = self.bracket_partners[word_index] {
+                return Some((self.words[word_index].index, self.words[partner_index].index));
+            }
+        }
+
+        return None;
+    }
+
+    pub fn matching_bracket(&self, index: usize) -> Option<(usize, usize)> {
+        match self.bracket_at(index) {
+            Some(brackets) => return Some(brackets),
+            None if index > 0 => return self.bracket_at(index - 1),
+            None => return None,
+        }
+    }
+
+    fn enclosing_pair(words: &Vec<Word>, bracket_partners: &Vec<Option<usize>>, index: usize) -> Option<(usize, usize)> {
+        let mut word_index = words.partition_point(|word| word.index < index);
+
+        while word_index > 0 {
+            word_index -= 1;
+
+            match bracket_partners[word_index] {
+
+                Some(partner_index) if partner_index > word_index => {
+                    if words[partner_index].index >= index {
+                        return Some((words[word_index].index, words[partner_index].index));
+                    }
+                },
+
+                // a pair that closes before the index cannot enclose it, so skip its contents
+                Some(partner_index) => word_index = partner_index,
+
+                None => { },
+            }
+        }
+
+        return None;
+    }
+
+    pub fn enclosing_brackets(&self, index: usize) -> Option<(usize, usize)> {
+        return Self::enclosing_pair(&self.words, &self.bracket_partners, index);
+    }
+
     fn last_word_index(&self) -> usize {
         match self.words.is_empty() {
             true => return 0,
@@ -144,6 +408,7 @@ impl Filebuffer {
     }
 
     fn insert_text_raw(&mut self, index: usize, text: &SharedString) {
+        self.revision = next_revision();
         for offset in (0..text.len()).rev() {
             match offset == self.text.len() {
                 true => self.text.push(text[offset]),
@@ -153,6 +418,7 @@ impl Filebuffer {
     }
 
     fn remove_text_raw(&mut self, index: usize, length: usize) {
+        self.revision = next_revision();
         for _ in 0..length {
             self.text.remove(index);
         }
@@ -165,6 +431,36 @@ impl Filebuffer {
         }
     }
 
+    fn truncate_history(&mut self) {
+        if let Some(saved_index) = self.saved_index {
+            if saved_index > self.history_index {
+                self.saved_index = None;
+            }
+        }
+
+        self.history.pop_until(self.history_index);
+    }
+
+    pub fn mark_saved(&mut self) {
+        self.saved_index = Some(self.history_index);
+        self.saved_format = self.format;
+    }
+
+    pub fn is_modified(&self) -> bool {
+        if self.format != self.saved_format {
+            return true;
+        }
+
+        match self.saved_index {
+            Some(saved_index) => {
+                let start = min(saved_index, self.history_index);
+                let end = max(saved_index, self.history_index);
+                return (start..end).any(|index| self.history.get(index).is_text());
+            },
+            None => return true,
+        }
+    }
+
     fn advance(&mut self, offset: usize) -> usize {
         self.history_index += offset;
         return self.history_index;
@@ -172,7 +468,7 @@ impl Filebuffer {
 
     pub fn insert_text(&mut self, window_id: usize, index: usize, text: SharedString, combine: bool) -> usize {
         self.insert_text_raw(index, &text);
-        self.history.pop_until(self.history_index);
+        self.truncate_history();
         self.history.insert_text(window_id, index, text, combine);
         return self.advance(1);
     }
@@ -180,57 +476,77 @@ impl Filebuffer {
     pub fn remove_text(&mut self, window_id: usize, index: usize, length: usize, combine: bool) -> usize {
         let removed_text = self.text.slice(index, index + length - 1);
         self.remove_text_raw(index, length);
-        self.history.pop_until(self.history_index);
+        self.truncate_history();
         self.history.remove_text(window_id, removed_text, index, combine);
         return self.advance(1);
     }
 
     pub fn set_text(&mut self, window_id: usize, text: SharedString) -> usize {
-        self.history.pop_until(self.history_index);
+        self.truncate_history();
         self.history.remove_text(window_id, self.text.clone(), 0, false);
         self.history.insert_text(window_id, 0, text.clone(), false);
         self.text = text;
+        self.revision = next_revision();
         return self.advance(2);
     }
 
     pub fn add_selection(&mut self, window_id: usize, index: usize, primary_index: usize, secondary_index: usize, offset: usize, combine: bool) -> usize {
-        self.history.pop_until(self.history_index);
+        self.truncate_history();
         self.history.add_selection(window_id, index, primary_index, secondary_index, offset, combine);
         return self.advance(1);
     }
 
     pub fn remove_selection(&mut self, window_id: usize, index: usize, primary_index: usize, secondary_index: usize, offset: usize, combine: bool) -> usize {
-        self.history.pop_until(self.history_index);
+        self.truncate_history();
         self.history.remove_selection(window_id, index, primary_index, secondary_index, offset, combine);
         return self.advance(1);
     }
 
     pub fn change_primary_index(&mut self, window_id: usize, index: usize, previous: usize, new: usize, combine: bool) -> usize {
-        self.history.pop_until(self.history_index);
+        self.truncate_history();
         self.history.change_primary_index(window_id, index, previous, new, combine);
         return self.advance(1);
     }
 
     pub fn change_secondary_index(&mut self, window_id: usize, index: usize, previous: usize, new: usize, combine: bool) -> usize {
-        self.history.pop_until(self.history_index);
+        self.truncate_history();
         self.history.change_secondary_index(window_id, index, previous, new, combine);
         return self.advance(1);
     }
 
     pub fn change_offset(&mut self, window_id: usize, index: usize, previous: usize, new: usize, combine: bool) -> usize {
-        self.history.pop_until(self.history_index);
+        self.truncate_history();
         self.history.change_offset(window_id, index, previous, new, combine);
         return self.advance(1);
     }
 
     pub fn change_selection_mode(&mut self, window_id: usize, previous: SelectionMode, new: SelectionMode, combine: bool) -> usize {
-        self.history.pop_until(self.history_index);
+        self.truncate_history();
         self.history.change_selection_mode(window_id, previous, new, combine);
         return self.advance(1);
     }
 
+    pub fn changed_text_since(&self, history_index: usize) -> bool {
+        return (history_index..self.history_index).any(|index| self.history.get(index).is_text());
+    }
+
+    pub fn begin_group(&mut self) {
+        let history_index = self.history_index;
+        self.begin_group_at(history_index);
+    }
+
+    pub fn begin_group_at(&mut self, history_index: usize) {
+        self.truncate_history();
+        self.history.begin_group(history_index);
+    }
+
+    pub fn end_group(&mut self) {
+        self.history.end_group();
+    }
+
     pub fn set_text_without_save(&mut self, text: SharedString) {
         self.text = text;
+        self.revision = next_revision();
     }
 
     pub fn get_text(&self) -> SharedString {
@@ -249,6 +565,36 @@ impl Filebuffer {
         return self.text[index];
     }
 
+    fn continues_cluster(&self, index: usize) -> bool {
+        return is_grapheme_extend(self.text[index]) || (index > 0 && is_joiner(self.text[index - 1]));
+    }
+
+    pub fn next_cluster(&self, index: usize) -> usize {
+        let mut next_index = index + 1;
+        while next_index < self.last_buffer_index() && self.continues_cluster(next_index) {
+            next_index += 1;
+        }
+        return next_index;
+    }
+
+    pub fn previous_cluster(&self, index: usize) -> usize {
+        let mut previous_index = index - 1;
+        while previous_index > 0 && self.continues_cluster(previous_index) {
+            previous_index -= 1;
+        }
+        return previous_index;
+    }
+
+    pub fn cluster_text(&self, index: usize) -> SharedString {
+        let mut text = self.text[index].to_string();
+        let mut next_index = index + 1;
+        while next_index < self.text.len() && self.continues_cluster(next_index) {
+            text.push(self.text[next_index]);
+            next_index += 1;
+        }
+        return text;
+    }
+
     pub fn last_character(&self) -> Character {
         return self.text[self.last_buffer_index()];
     }
@@ -356,3 +702,65 @@ impl Filebuffer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn settings() -> LanguageSettings {
+        let mut settings = LanguageSettings::new();
+        settings.bracket_operators = vec![
+            (SharedString::from("("), SharedString::from(")")),
+            (SharedString::from("["), SharedString::from("]")),
+        ];
+        return settings;
+    }
+
+    fn words(text: &str) -> Vec<Word> {
+        return text.char_indices()
+            .filter(|(_index, character)| !character.is_whitespace())
+            .map(|(index, character)| Word::new(TokenType::Operator(SharedString::from(character.to_string().as_str())), index, 1))
+            .collect();
+    }
+
+    #[test]
+    fn mismatched_kind_inside_pair() {
+        let (bracket_partners, unmatched_brackets, bracket_depths) = Filebuffer::match_brackets(&words("( ] )"), &settings());
+        assert_eq!(bracket_partners, vec![Some(2), None, Some(0)]);
+        assert_eq!(unmatched_brackets, vec![1]);
+        assert_eq!(bracket_depths, vec![BracketDepth::Nested(0), BracketDepth::Unbalanced, BracketDepth::Nested(0)]);
+    }
+
+    #[test]
+    fn crossed_pairs() {
+        let (bracket_partners, unmatched_brackets, _bracket_depths) = Filebuffer::match_brackets(&words("( [ ) ]"), &settings());
+        assert_eq!(bracket_partners, vec![None, Some(3), None, Some(1)]);
+        assert_eq!(unmatched_brackets, vec![0, 2]);
+    }
+
+    #[test]
+    fn unmatched_notes() {
+        let text = SharedString::from("(\n]\n[ ]\n)");
+        let words = words(&text.serialize());
+        let (_bracket_partners, unmatched_brackets, _bracket_depths) = Filebuffer::match_brackets(&words, &settings());
+        let notes = Filebuffer::bracket_notes(&text, &words, &unmatched_brackets);
+
+        assert_eq!(notes.len(), 1);
+        assert_eq!(notes[0].line, 2);
+        assert_eq!(notes[0].index, 2);
+        assert_eq!(notes[0].message.serialize(), "unmatched ]");
+    }
+
+    #[test]
+    fn enclosing_pairs() {
+        let words = words("( [ a ] ( ) b )");
+        let (bracket_partners, _unmatched_brackets, _bracket_depths) = Filebuffer::match_brackets(&words, &settings());
+
+        assert_eq!(Filebuffer::enclosing_pair(&words, &bracket_partners, 4), Some((2, 6)));
+        assert_eq!(Filebuffer::enclosing_pair(&words, &bracket_partners, 12), Some((0, 14)));
+        assert_eq!(Filebuffer::enclosing_pair(&words, &bracket_partners, 10), Some((8, 10)));
+        assert_eq!(Filebuffer::enclosing_pair(&words, &bracket_partners, 0), None);
+        assert_eq!(Filebuffer::enclosing_pair(&words, &bracket_partners, 15), None);
+    }
+}
@@ -0,0 +1,19 @@
+use seamonkey::*;
+
+#[derive(Clone, Debug)]
+pub struct BracketNote {
+    pub line: usize,
+    pub index: usize,
+    pub message: SharedString,
+}
+
+impl BracketNote {
+
+    pub fn new(line: usize, index: usize, bracket: SharedString) -> Self {
+        return Self {
+            line: line,
+            index: index,
+            message: format_shared!("unmatched {}", bracket),
+        }
+    }
+}
@@ -1,159 +1,179 @@
 use seamonkey::*;
 
-#[derive(Copy, Clone, Debug, PartialEq, Hash)]
-pub enum Action {
-    Quit,
-    Reload,
-    ToggleAppendLines,
-    ToggleStatusBar,
-    ToggleLineNumbers,
-    ToggleSelectionLines,
-    ToggleHighlighting,
-    TogglePreserveLines,
-    ToggleUnfocusedSelections,
-    ToggleHiddenFiles,
-    ToggleStartAtSymbol,
-    ToggleRelativeLineNumbers,
-    CharacterMode,
-    WordMode,
-    LineMode,
-    NewFile,
-    Open,
-    Filebuffers,
-    Notes,
-    SaveFile,
-    Language,
-    Theme,
-    Replace,
-    Down,
-    Up,
-    Left,
-    Right,
-    Start,
-    End,
-    ExtendStart,
-    ExtendEnd,
-    AddSelection,
-    SelectNext,
-    FocusNext,
-    Action,
-    Abort,
-    Confirm,
-    Remove,
-    RemoveSection,
-    Delete,
-    DeleteLine,
-    ZoomIn,
-    ZoomOut,
-    IncreaseAntialiasing,
-    DecreaseAntialiasing,
-    NewWindow,
-    CloseWindow,
-    PageUp,
-    PageDown,
-    ExtendPageUp,
-    ExtendPageDown,
-    DuplicateUp,
-    DuplicateDown,
-    Insert,
-    Append,
-    NewlineUp,
-    NewlineDown,
-    ExtendLeft,
-    ExtendRight,
-    ExtendUp,
-    ExtendDown,
-    MoveLeft,
-    MoveRight,
-    MoveUp,
-    MoveDown,
-    Copy,
-    Paste,
-    Cut,
-    Rotate,
-    Undo,
-    Redo,
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ActionCategory {
+    Window,
+    View,
+    File,
+    Dialogue,
+    Navigation,
+    Selection,
+    Editing,
+}
+
+impl ActionCategory {
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ActionCategory::Window => return "window",
+            ActionCategory::View => return "view",
+            ActionCategory::File => return "file",
+            ActionCategory::Dialogue => return "dialogue",
+            ActionCategory::Navigation => return "navigation",
+            ActionCategory::Selection => return "selection",
+            ActionCategory::Editing => return "editing",
+        }
+    }
+}
+
+pub struct ActionInfo {
+    pub action: Action,
+    pub literal: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub category: ActionCategory,
+}
+
+macro_rules! register_actions {
+    ($($action: ident, $literal: expr, $name: expr, $description: expr, $category: ident;)*) => {
+
+        #[derive(Copy, Clone, Debug, PartialEq, Hash)]
+        pub enum Action {
+            $($action,)*
+        }
+
+        pub const ACTIONS: &'static [ActionInfo] = &[
+            $(ActionInfo {
+                action: Action::$action,
+                literal: $literal,
+                name: $name,
+                description: $description,
+                category: ActionCategory::$category,
+            },)*
+        ];
+    }
+}
+
+register_actions! {
+    Quit,                      "quit",                  "quit",                         "quit the editor", Window;
+    Reload,                    "reload",                "reload",                       "reload theme and bindings", Window;
+    ToggleAppendLines,         "append_lines",          "toggle append lines",          "toggle appending lines when moving past the end", View;
+    ToggleStatusBar,           "status_bar",            "toggle status bar",            "show or hide the status bar", View;
+    ToggleLineNumbers,         "line_numbers",          "toggle line numbers",          "show or hide line numbers", View;
+    ToggleSelectionLines,      "selection_lines",       "toggle selection lines",       "highlight lines that contain a selection", View;
+    ToggleHighlighting,        "highlighting",          "toggle highlighting",          "enable or disable syntax highlighting", View;
+    TogglePreserveLines,       "preserve_lines",        "toggle preserve lines",        "keep the column when moving between lines", View;
+    ToggleUnfocusedSelections, "unfocused_selections",  "toggle unfocused selections",  "show selections in unfocused windows", View;
+    ToggleHiddenFiles,         "hidden_files",          "toggle hidden files",          "show or hide hidden files in the file dialogue", Dialogue;
+    ToggleStartAtSymbol,       "start_at_symbol",       "toggle start at symbol",       "jump to the first symbol of a line on start", View;
+    ToggleRelativeLineNumbers, "relative_line_numbers", "toggle relative line numbers", "show line numbers relative to the cursor", View;
+    ToggleSoftWrap,            "soft_wrap",             "toggle soft wrap",             "wrap long lines at the window edge", View;
+    ToggleModal,               "modal_editing",         "toggle modal editing",         "switch between modeless and modal editing", View;
+    ToggleAutoClose,           "auto_close",            "toggle auto close",            "insert closing brackets and quotes while typing", View;
+    NormalMode,                "normal_mode",           "normal mode",                  "leave insert mode in modal editing", Editing;
+    InsertMode,                "insert_mode",           "insert mode",                  "type text in modal editing", Editing;
+    CharacterMode,             "character_mode",        "character mode",               "select single characters", Selection;
+    WordMode,                  "word_mode",             "word mode",                    "select whole words", Selection;
+    LineMode,                  "line_mode",             "line mode",                    "select whole lines", Selection;
+    NewFile,                   "new_file",              "new file",                     "create a new unnamed buffer", File;
+    Open,                      "open_file",             "open file",                    "open a file from disk", File;
+    Recent,                    "recent_files",          "recent files",                 "open a recently used file", File;
+    FindFile,                  "find_file",             "find file",                    "fuzzy find a file in the project", File;
+    BindingReport,             "binding_report",        "binding report",               "show problems in the bindings file", Window;
+    CheatSheet,                "cheat_sheet",           "cheat sheet",                  "list all key bindings by scope", Window;
+    EditBinding,               "edit_binding",          "edit binding",                 "capture a new key binding for an action", Window;
+    Filebuffers,               "loaded_buffers",        "loaded buffers",               "switch between loaded buffers", File;
+    Notes,                     "notes",                 "notes",                        "list notes of the current buffer", File;
+    SaveFile,                  "save_file",             "save file",                    "write the current buffer to disk", File;
+    ToggleLineEnding,          "toggle_line_ending",    "toggle line ending",           "switch the current buffer between lf and crlf line endings", File;
+    ToggleByteOrderMark,       "toggle_byte_order_mark", "toggle byte order mark",      "add or remove the byte order mark of the current buffer", File;
+    ToggleFinalNewline,        "toggle_final_newline",  "toggle final newline",         "save the current buffer with or without a final newline", File;
+    NextEncoding,              "next_encoding",         "next encoding",                "cycle the encoding the current buffer is saved with", File;
+    CloseBuffer,               "close_buffer",          "close buffer",                 "close the current buffer", File;
+    Language,                  "set_language",          "set language",                 "change the language of the current buffer", File;
+    Theme,                     "set_theme",             "set theme",                    "change the interface theme", Window;
+    Replace,                   "find_replace",          "find replace",                 "find and replace text", Editing;
+    Down,                      "down",                  "down",                         "move down", Navigation;
+    Up,                        "up",                    "up",                           "move up", Navigation;
+    Left,                      "left",                  "left",                         "move left", Navigation;
+    Right,                     "right",                 "right",                        "move right", Navigation;
+    Start,                     "start",                 "start",                        "move to the start of the line", Navigation;
+    End,                       "end",                   "end",                          "move to the end of the line", Navigation;
+    ExtendStart,               "extend_start",          "extend start",                 "extend the selection to the start of the line", Selection;
+    ExtendEnd,                 "extend_end",            "extend end",                   "extend the selection to the end of the line", Selection;
+    AddSelection,              "add_selection",         "add selection",                "add a new selection", Selection;
+    SelectNext,                "select_next",           "select next",                  "select the next occurrence", Selection;
+    FocusNext,                 "focus_next",            "focus next",                   "focus the next element", Dialogue;
+    Action,                    "action",                "action",                       "open the action palette", Dialogue;
+    Abort,                     "abort",                 "abort",                        "close the current dialogue", Dialogue;
+    Confirm,                   "confirm",               "confirm",                      "confirm the current dialogue", Dialogue;
+    Remove,                    "remove",                "remove",                       "remove the character before the cursor", Editing;
+    RemoveSection,             "remove_section",        "remove section",               "remove the last section", Editing;
+    Delete,                    "delete",                "delete",                       "delete the character after the cursor", Editing;
+    DeleteLine,                "delete_line",           "delete line",                  "delete the selected lines", Editing;
+    ZoomIn,                    "zoom_in",               "zoom in",                      "increase the font size", Window;
+    ZoomOut,                   "zoom_out",              "zoom out",                     "decrease the font size", Window;
+    IncreaseTabWidth,          "increase_tab_width",    "increase tab width",           "show tabs one column wider", View;
+    DecreaseTabWidth,          "decrease_tab_width",    "decrease tab width",           "show tabs one column narrower", View;
+    IncreaseAntialiasing,      "increase_antialiasing", "increase antialiasing",        "increase the antialiasing level", Window;
+    DecreaseAntialiasing,      "decrease_antialiasing", "decrease antialiasing",        "decrease the antialiasing level", Window;
+    NewWindow,                 "new_editor",            "new window",                   "open a new window", Window;
+    CloseWindow,               "close_window",          "close window",                 "close the current window", Window;
+    JumpToMatchingBracket,     "jump_to_matching_bracket", "jump to matching bracket",   "move each selection to the bracket matching the one next to it", Navigation;
+    SelectInsideBrackets,      "select_inside_brackets", "select inside brackets",      "select the text inside the brackets around each selection", Selection;
+    PageUp,                    "page_up",               "page up",                      "move up one page", Navigation;
+    PageDown,                  "page_down",             "page down",                    "move down one page", Navigation;
+    ExtendPageUp,              "extend_page_up",        "extend page up",               "extend the selection up one page", Selection;
+    ExtendPageDown,            "extend_page_down",      "extend page down",             "extend the selection down one page", Selection;
+    DuplicateUp,               "duplicate_up",          "duplicate up",                 "duplicate the selection above", Editing;
+    DuplicateDown,             "duplicate_down",        "duplicate down",               "duplicate the selection below", Editing;
+    Insert,                    "insert",                "insert",                       "move to the start of the selection", Selection;
+    Append,                    "append",                "append",                       "move to the end of the selection", Selection;
+    NewlineUp,                 "newline_up",            "newline up",                   "insert a line above", Editing;
+    NewlineDown,               "newline_down",          "newline down",                 "insert a line below", Editing;
+    ExtendLeft,                "extend_left",           "extend left",                  "extend the selection left", Selection;
+    ExtendRight,               "extend_right",          "extend right",                 "extend the selection right", Selection;
+    ExtendUp,                  "extend_up",             "extend up",                    "extend the selection up", Selection;
+    ExtendDown,                "extend_down",           "extend down",                  "extend the selection down", Selection;
+    MoveLeft,                  "move_left",             "move left",                    "move the selection left", Editing;
+    MoveRight,                 "move_right",            "move right",                   "move the selection right", Editing;
+    MoveUp,                    "move_up",               "move up",                      "move the selection up", Editing;
+    MoveDown,                  "move_down",             "move down",                    "move the selection down", Editing;
+    Copy,                      "copy",                  "copy",                         "copy the selection", Editing;
+    Paste,                     "paste",                 "paste",                        "paste the clipboard", Editing;
+    Cut,                       "cut",                   "cut",                          "cut the selection", Editing;
+    Rotate,                    "rotate",                "rotate",                       "rotate the selected text", Editing;
+    TabsToSpaces,              "tabs_to_spaces",        "tabs to spaces",               "indent the selected lines or the whole buffer with spaces", Editing;
+    SpacesToTabs,              "spaces_to_tabs",        "spaces to tabs",               "indent the selected lines or the whole buffer with tabs", Editing;
+    Indent,                    "indent",                "indent",                       "indent the selected lines", Editing;
+    Dedent,                    "dedent",                "dedent",                       "dedent the selected lines", Editing;
+    ToggleComment,             "toggle_comment",        "toggle comment",               "comment or uncomment the selected lines", Editing;
+    ToggleBlockComment,        "toggle_block_comment",  "toggle block comment",         "wrap or unwrap the selected text in a block comment", Editing;
+    Undo,                      "undo",                  "undo",                         "undo the last change", Editing;
+    Redo,                      "redo",                  "redo",                         "redo the last undone change", Editing;
+    RecordMacro,               "record_macro",          "record macro",                 "start or stop recording a macro", Editing;
+    PlayMacro,                 "play_macro",            "play macro",                   "replay the last recorded macro", Editing;
+    PlayMacroSelections,       "play_macro_selections", "play macro per selection",     "replay the last macro once for every selection", Editing;
+    NameMacro,                 "name_macro",            "name macro",                   "save the last recorded macro under a name", Editing;
+    RepeatLast,                "repeat_last",           "repeat last",                  "repeat the last edit at the current selections", Editing;
+    Count,                     "count",                 "count",                        "enter a repeat count for the next action", Editing;
 }
 
 impl Action {
 
     pub fn from_literal(literal: &SharedString) -> Status<Self> {
-        match literal.printable().as_str() {
-            "quit" => return success!(Action::Quit),
-            "reload" => return success!(Action::Reload),
-            "append_lines" => return success!(Action::ToggleAppendLines),
-            "status_bar" => return success!(Action::ToggleStatusBar),
-            "line_numbers" => return success!(Action::ToggleLineNumbers),
-            "selection_lines" => return success!(Action::ToggleSelectionLines),
-            "highlighting" => return success!(Action::ToggleHighlighting),
-            "unfocused_selections" => return success!(Action::ToggleUnfocusedSelections),
-            "hidden_files" => return success!(Action::ToggleHiddenFiles),
-            "start_at_symbol" => return success!(Action::ToggleStartAtSymbol),
-            "relative_line_numbers" => return success!(Action::ToggleRelativeLineNumbers),
-            "preserve_lines" => return success!(Action::TogglePreserveLines),
-            "character_mode" => return success!(Action::CharacterMode),
-            "word_mode" => return success!(Action::WordMode),
-            "line_mode" => return success!(Action::LineMode),
-            "new_file" => return success!(Action::NewFile),
-            "open_file" => return success!(Action::Open),
-            "loaded_buffers" => return success!(Action::Filebuffers),
-            "notes" => return success!(Action::Notes),
-            "save_file" => return success!(Action::SaveFile),
-            "set_language" => return success!(Action::Language),
-            "set_theme" => return success!(Action::Theme),
-            "find_replace" => return success!(Action::Replace),
-            "start" => return success!(Action::Start),
-            "end" => return success!(Action::End),
-            "extend_start" => return success!(Action::ExtendStart),
-            "extend_end" => return success!(Action::ExtendEnd),
-            "add_selection" => return success!(Action::AddSelection),
-            "select_next" => return success!(Action::SelectNext),
-            "focus_next" => return success!(Action::FocusNext),
-            "action" => return success!(Action::Action),
-            "abort" => return success!(Action::Abort),
-            "confirm" => return success!(Action::Confirm),
-            "remove" => return success!(Action::Remove),
-            "remove_section" => return success!(Action::RemoveSection),
-            "delete" => return success!(Action::Delete),
-            "delete_line" => return success!(Action::DeleteLine),
-            "zoom_in" => return success!(Action::ZoomIn),
-            "zoom_out" => return success!(Action::ZoomOut),
-            "increase_antialiasing" => return success!(Action::IncreaseAntialiasing),
-            "decrease_antialiasing" => return success!(Action::DecreaseAntialiasing),
-            "new_editor" => return success!(Action::NewWindow),
-            "close_window" => return success!(Action::CloseWindow),
-            "page_up" => return success!(Action::PageUp),
-            "page_down" => return success!(Action::PageDown),
-            "extend_page_up" => return success!(Action::ExtendPageUp),
-            "extend_page_down" => return success!(Action::ExtendPageDown),
-            "duplicate_up" => return success!(Action::DuplicateUp),
-            "duplicate_down" => return success!(Action::DuplicateDown),
-            "insert" => return success!(Action::Insert),
-            "append" => return success!(Action::Append),
-            "newline_up" => return success!(Action::NewlineUp),
-            "newline_down" => return success!(Action::NewlineDown),
-            "left" => return success!(Action::Left),
-            "right" => return success!(Action::Right),
-            "up" => return success!(Action::Up),
-            "down" => return success!(Action::Down),
-            "extend_left" => return success!(Action::ExtendLeft),
-            "extend_right" => return success!(Action::ExtendRight),
-            "extend_up" => return success!(Action::ExtendUp),
-            "extend_down" => return success!(Action::ExtendDown),
-            "move_left" => return success!(Action::MoveLeft),
-            "move_right" => return success!(Action::MoveRight),
-            "move_up" => return success!(Action::MoveUp),
-            "move_down" => return success!(Action::MoveDown),
-            "copy" => return success!(Action::Copy),
-            "paste" => return success!(Action::Paste),
-            "cut" => return success!(Action::Cut),
-            "rotate" => return success!(Action::Rotate),
-            "undo" => return success!(Action::Undo),
-            "redo" => return success!(Action::Redo),
-            invalid => return error!(string!("invalid action {}", invalid)),
+        let printable = literal.printable();
+
+        match ACTIONS.iter().find(|info| info.literal == printable.as_str()) {
+            Some(info) => return success!(info.action),
+            None => return error!(string!("invalid action {}", printable)),
         }
     }
 
+    pub fn info(&self) -> &'static ActionInfo {
+        return ACTIONS.iter().find(|info| info.action == *self).unwrap();
+    }
+
     pub fn is_global(&self) -> bool {
         match self {
             Action::Quit => return true,
@@ -167,8 +187,13 @@ impl Action {
             Action::ToggleStartAtSymbol => return true,
             Action::TogglePreserveLines => return true,
             Action::ToggleRelativeLineNumbers => return true,
+            Action::ToggleModal => return true,
+            Action::ToggleSoftWrap => return true,
+            Action::ToggleAutoClose => return true,
             Action::ZoomIn => return true,
             Action::ZoomOut => return true,
+            Action::IncreaseTabWidth => return true,
+            Action::DecreaseTabWidth => return true,
             Action::IncreaseAntialiasing => return true,
             Action::DecreaseAntialiasing => return true,
             Action::NewWindow => return true,
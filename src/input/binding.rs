@@ -1,5 +1,6 @@
+use seamonkey::*;
 use sfml::window::Key;
-use super::Modifiers;
+use super::{ Modifiers, literal_from_key };
 
 macro_rules! match_modifier_state {
     ($modifiers: expr, $field: ident, $state:expr) => ({
@@ -30,6 +31,53 @@ impl Binding {
         return self.included.length() + self.excluded.length();
     }
 
+    pub fn is_contradictory(&self) -> bool {
+        return self.included.intersects(&self.excluded);
+    }
+
+    pub fn overlaps(&self, other: &Binding) -> bool {
+        if self.trigger != other.trigger || self.is_contradictory() || other.is_contradictory() {
+            return false;
+        }
+
+        return !self.included.intersects(&other.excluded) && !self.excluded.intersects(&other.included);
+    }
+
+    pub fn specializes(&self, other: &Binding) -> bool {
+        return self.trigger == other.trigger && self.included.contains(&other.included) && self.excluded.contains(&other.excluded);
+    }
+
+    pub fn is_comparable(&self, other: &Binding) -> bool {
+        return self.included.contains(&other.included) || other.included.contains(&self.included);
+    }
+
+    pub fn serialize(&self) -> String {
+        let mut serialized = self.included.display().serialize();
+
+        for modifier in self.excluded.display().serialize().split_whitespace() {
+            serialized.push_str(&format!("#{} ", modifier));
+        }
+
+        let literal = literal_from_key(self.trigger);
+        match literal.chars().count() {
+            1 => serialized.push_str(&format!("'{}'", literal)),
+            _other => serialized.push_str(literal),
+        }
+
+        return serialized;
+    }
+
+    pub fn display(&self) -> SharedString {
+        let mut display = self.included.display();
+
+        for modifier in self.excluded.display().serialize().split_whitespace() {
+            display.push_str(&format_shared!("#{} ", modifier));
+        }
+
+        display.push_str(&SharedString::from(literal_from_key(self.trigger)));
+        return display;
+    }
+
     pub fn matches(&self, trigger: &Key, modifiers: &Modifiers) -> bool {
 
         if self.trigger != *trigger {
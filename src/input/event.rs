@@ -1,6 +1,6 @@
 use seamonkey::*;
 use sfml::window::Key;
-use super::Modifiers;
+use super::{ Modifiers, literal_from_key };
 
 #[derive(Copy, Clone, Debug)]
 pub struct KeyEvent {
@@ -16,4 +16,10 @@ impl KeyEvent {
             modifiers: modifiers,
         }
     }
+
+    pub fn display(&self) -> SharedString {
+        let mut display = self.modifiers.display();
+        display.push_str(&SharedString::from(literal_from_key(self.trigger)));
+        return display;
+    }
 }
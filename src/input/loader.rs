@@ -0,0 +1,430 @@
+use seamonkey::*;
+
+use sfml::window::Key;
+
+use super::{ Action, Binding, BindingProblem, BindingScope, Modifiers, is_modifier_key, key_from_literal };
+
+const DEFAULT_COUNT_MODIFIERS: Modifiers = Modifiers { shift: false, control: false, alt: true, system: false };
+
+struct LineLocator {
+    lines: Vec<Vec<String>>,
+}
+
+impl LineLocator {
+
+    fn new(text: &str) -> Self {
+        let lines = text.lines().map(|line| {
+            line.split(|character: char| character.is_whitespace() || "{}[]".contains(character))
+                .filter(|token| !token.is_empty())
+                .map(|token| token.to_string())
+                .collect()
+        }).collect();
+
+        return Self {
+            lines: lines,
+        };
+    }
+
+    fn find_entry(&self, start: usize, name: &str) -> Option<usize> {
+        return (start..self.lines.len()).find(|index| self.lines[*index].first().map(|token| token == name).unwrap_or(false));
+    }
+
+    fn find_token(&self, start: usize, name: &str) -> Option<usize> {
+        return (start..self.lines.len()).find(|index| self.lines[*index].iter().any(|token| token == name));
+    }
+
+    fn section_line(&self, scope: &BindingScope) -> Option<usize> {
+        match scope {
+            BindingScope::Global => return self.find_entry(0, "#bindings"),
+            BindingScope::Normal => return self.find_entry(0, "#normal"),
+            BindingScope::Syntax(language) => match self.find_entry(0, "#languages") {
+                Some(start) => return self.find_entry(start + 1, &language.printable()),
+                None => return None,
+            },
+            other => match self.find_entry(0, "#scopes") {
+                Some(start) => return self.find_entry(start + 1, &other.name().printable()),
+                None => return None,
+            },
+        }
+    }
+
+    fn action_line(&self, section_line: Option<usize>, action: &str) -> Option<usize> {
+        match section_line {
+            Some(start) => return self.find_token(start, action).map(|index| index + 1),
+            None => return None,
+        }
+    }
+}
+
+fn parse_binding(binding: &Data) -> Status<Binding> {
+    let mut trigger = None;
+    let mut included = Modifiers::new();
+    let mut excluded = Modifiers::new();
+
+    let binding_keys_list = unpack_list!(binding);
+    for binding_key in binding_keys_list.iter() {
+
+        let key = confirm!(key_from_literal(&unpack_literal!(binding_key)));
+
+        if binding_key.is_keyword() {
+            if is_modifier_key(key) {
+                match key {
+                    Key::LSHIFT => excluded.shift = true,
+                    Key::LCONTROL => excluded.control = true,
+                    Key::LALT => excluded.alt = true,
+                    Key::LSYSTEM => excluded.system = true,
+                    _other => panic!(),
+                }
+            } else {
+                return error!(string!("only modifiers can be excluded in bindings"));
+            }
+
+        } else {
+            if is_modifier_key(key) {
+                match key {
+                    Key::LSHIFT => included.shift = true,
+                    Key::LCONTROL => included.control = true,
+                    Key::LALT => included.alt = true,
+                    Key::LSYSTEM => included.system = true,
+                    _other => panic!(),
+                }
+            } else if trigger.is_some() {
+                return error!(string!("keybinding can only have one trigger"));
+            } else {
+                trigger = Some(key);
+            }
+        }
+    }
+
+    let trigger = expect!(trigger, string!("keybinding must have a trigger"));
+    return success!(Binding::new(trigger, included, excluded));
+}
+
+fn parse_sequence(binding: &Data) -> Status<Vec<Binding>> {
+    let binding_keys_list = unpack_list!(binding);
+    let mut sequence = Vec::new();
+
+    match binding_keys_list.iter().any(|binding_key| binding_key.is_list()) {
+        true => {
+            for combination in binding_keys_list.iter() {
+                sequence.push(confirm!(parse_binding(combination)));
+            }
+        },
+        false => sequence.push(confirm!(parse_binding(binding))),
+    }
+
+    return success!(sequence);
+}
+
+pub fn sequence_length(sequence: &[Binding]) -> usize {
+    return sequence.iter().map(|binding| binding.length()).sum();
+}
+
+pub fn display_sequence(sequence: &[Binding]) -> SharedString {
+    let mut display = SharedString::new();
+
+    for (index, binding) in sequence.iter().enumerate() {
+        if index > 0 {
+            display.push_str(&SharedString::from(", "));
+        }
+        display.push_str(&binding.display());
+    }
+
+    return display;
+}
+
+fn sequences_overlap(left: &[Binding], right: &[Binding]) -> bool {
+    return left.iter().zip(right.iter()).all(|(left, right)| left.overlaps(right) && left.is_comparable(right));
+}
+
+fn sequence_specializes(left: &[Binding], right: &[Binding]) -> bool {
+    return left.len() == right.len() && left.iter().zip(right.iter()).all(|(left, right)| left.specializes(right));
+}
+
+fn validate_sequence(bindings: &Vec<(BindingScope, Vec<Binding>, Action)>, scope: &BindingScope, sequence: &Vec<Binding>, action: Action) -> Vec<SharedString> {
+    let mut problems = Vec::new();
+
+    if sequence.iter().any(|binding| binding.is_contradictory()) {
+        problems.push(format_shared!("{} for {} includes and excludes the same modifier and can never match", display_sequence(sequence), action.info().literal));
+    }
+
+    for (other_scope, other, other_action) in bindings.iter() {
+        if other_scope != scope {
+            continue;
+        }
+
+        if other == sequence {
+            match *other_action == action {
+                true => problems.push(format_shared!("duplicate binding {} for {}", display_sequence(sequence), action.info().literal)),
+                false => problems.push(format_shared!("{} is bound to both {} and {}", display_sequence(sequence), other_action.info().literal, action.info().literal)),
+            }
+            continue;
+        }
+
+        if !sequences_overlap(other, sequence) {
+            continue;
+        }
+
+        if other.len() != sequence.len() {
+            let (prefix, prefix_action, longer, longer_action) = match other.len() < sequence.len() {
+                true => (other, *other_action, sequence, action),
+                false => (sequence, action, other, *other_action),
+            };
+
+            problems.push(format_shared!("{} ({}) shadows {} ({})", display_sequence(prefix), prefix_action.info().literal, display_sequence(longer), longer_action.info().literal));
+            continue;
+        }
+
+        if *other_action != action && !sequence_specializes(other, sequence) && !sequence_specializes(sequence, other) {
+            let (winner, winner_action, loser, loser_action) = match sequence_length(other) > sequence_length(sequence) {
+                true => (other, *other_action, sequence, action),
+                false => (sequence, action, other, *other_action),
+            };
+
+            problems.push(format_shared!("{} ({}) shadows {} ({})", display_sequence(winner), winner_action.info().literal, display_sequence(loser), loser_action.info().literal));
+        }
+    }
+
+    return problems;
+}
+
+fn parse_bindings(bindings: &mut Vec<(BindingScope, Vec<Binding>, Action)>, problems: &mut Vec<BindingProblem>, locator: &LineLocator, scope: BindingScope, entry: &Data) -> Status<()> {
+    let section_line = locator.section_line(&scope);
+
+    for (key, value) in unpack_map!(entry).iter() {
+        let literal = unpack_literal!(key);
+        let line = locator.action_line(section_line, &literal.printable());
+
+        let action = match Action::from_literal(&literal) {
+            Status::Success(action) => action,
+            Status::Error(error) => {
+                problems.push(BindingProblem::from_error(line, error));
+                continue;
+            },
+        };
+
+        let bindings_list = unpack_list!(value);
+        for binding in bindings_list.iter() {
+
+            let sequence = match parse_sequence(binding) {
+                Status::Success(sequence) => sequence,
+                Status::Error(error) => {
+                    problems.push(BindingProblem::from_error(line, error));
+                    continue;
+                },
+            };
+
+            for message in validate_sequence(bindings, &scope, &sequence, action) {
+                problems.push(BindingProblem::new(line, message));
+            }
+
+            let new_length = sequence_length(&sequence);
+            match bindings.iter().position(|(_, other, _)| sequence_length(other) <= new_length) {
+                Some(index) => bindings.insert(index, (scope.clone(), sequence, action)),
+                None => bindings.push((scope.clone(), sequence, action)),
+            }
+        }
+    }
+
+    return success!(());
+}
+
+fn parse_section(bindings: &mut Vec<(BindingScope, Vec<Binding>, Action)>, problems: &mut Vec<BindingProblem>, locator: &LineLocator, scope: BindingScope, entry: &Data) {
+    let section_line = locator.section_line(&scope).map(|index| index + 1);
+
+    if let Status::Error(error) = parse_bindings(bindings, problems, locator, scope, entry) {
+        problems.push(BindingProblem::from_error(section_line, error));
+    }
+}
+
+fn parse_scopes(bindings: &mut Vec<(BindingScope, Vec<Binding>, Action)>, problems: &mut Vec<BindingProblem>, locator: &LineLocator, entry: &Data, languages: bool) -> Status<()> {
+    for (key, value) in unpack_map!(entry).iter() {
+        let literal = unpack_literal!(key);
+
+        let scope = match languages {
+            true => BindingScope::Syntax(literal),
+            false => match BindingScope::from_literal(&literal) {
+                Status::Success(scope) => scope,
+                Status::Error(error) => {
+                    let line = locator.find_entry(0, "#scopes").and_then(|start| locator.find_entry(start + 1, &literal.printable())).map(|index| index + 1);
+                    problems.push(BindingProblem::from_error(line, error));
+                    continue;
+                },
+            },
+        };
+
+        parse_section(bindings, problems, locator, scope, value);
+    }
+
+    return success!(());
+}
+
+fn load_scopes(bindings: &mut Vec<(BindingScope, Vec<Binding>, Action)>, problems: &mut Vec<BindingProblem>, locator: &LineLocator, entry: Status<Option<Data>>, section: &str, languages: bool) {
+    match entry {
+
+        Status::Success(Some(entry)) => {
+            if let Status::Error(error) = parse_scopes(bindings, problems, locator, &entry, languages) {
+                let line = locator.find_entry(0, section).map(|index| index + 1);
+                problems.push(BindingProblem::from_error(line, error));
+            }
+        },
+
+        Status::Success(None) => { },
+
+        Status::Error(error) => problems.push(BindingProblem::from_error(None, error)),
+    }
+}
+
+fn parse_count_modifiers(entry: &Data) -> Status<Modifiers> {
+    let mut modifiers = Modifiers::new();
+
+    for modifier in unpack_list!(entry).iter() {
+        match confirm!(key_from_literal(&unpack_literal!(modifier))) {
+            Key::LSHIFT => modifiers.shift = true,
+            Key::LCONTROL => modifiers.control = true,
+            Key::LALT => modifiers.alt = true,
+            Key::LSYSTEM => modifiers.system = true,
+            _other => return error!(string!("count modifiers can only contain modifier keys")),
+        }
+    }
+
+    if modifiers == Modifiers::new() {
+        return error!(string!("count modifiers must contain at least one modifier"));
+    }
+
+    return success!(modifiers);
+}
+
+pub fn load_bindings(bindings_file: &SharedString) -> (Vec<(BindingScope, Vec<Binding>, Action)>, Modifiers, Vec<BindingProblem>) {
+    let mut bindings = Vec::new();
+    let mut count_modifiers = DEFAULT_COUNT_MODIFIERS;
+    let mut problems = Vec::new();
+
+    let bindings_data = match read_map(bindings_file) {
+        Status::Success(bindings_data) => bindings_data,
+        Status::Error(error) => {
+            problems.push(BindingProblem::from_error(None, error));
+            return (bindings, count_modifiers, problems);
+        },
+    };
+
+    let locator = match read_file(bindings_file) {
+        Status::Success(text) => LineLocator::new(&text.serialize()),
+        Status::Error(_error) => LineLocator::new(""),
+    };
+
+    match bindings_data.index(&keyword!("bindings")) {
+        Status::Success(Some(bindings_entry)) => parse_section(&mut bindings, &mut problems, &locator, BindingScope::Global, &bindings_entry),
+        Status::Success(None) => problems.push(BindingProblem::new(None, SharedString::from("missing #bindings section"))),
+        Status::Error(error) => problems.push(BindingProblem::from_error(None, error)),
+    }
+
+    match bindings_data.index(&keyword!("normal")) {
+        Status::Success(Some(normal_entry)) => parse_section(&mut bindings, &mut problems, &locator, BindingScope::Normal, &normal_entry),
+        Status::Success(None) => { },
+        Status::Error(error) => problems.push(BindingProblem::from_error(None, error)),
+    }
+
+    let scopes_entry = bindings_data.index(&keyword!("scopes"));
+    load_scopes(&mut bindings, &mut problems, &locator, scopes_entry, "#scopes", false);
+
+    let languages_entry = bindings_data.index(&keyword!("languages"));
+    load_scopes(&mut bindings, &mut problems, &locator, languages_entry, "#languages", true);
+
+    match bindings_data.index(&keyword!("count_modifiers")) {
+        Status::Success(Some(count_entry)) => match parse_count_modifiers(&count_entry) {
+            Status::Success(modifiers) => count_modifiers = modifiers,
+            Status::Error(error) => problems.push(BindingProblem::from_error(locator.find_entry(0, "#count_modifiers").map(|index| index + 1), error)),
+        },
+        Status::Success(None) => { },
+        Status::Error(error) => problems.push(BindingProblem::from_error(None, error)),
+    }
+
+    return (bindings, count_modifiers, problems);
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn binding(trigger: Key, included: Modifiers, excluded: Modifiers) -> Binding {
+        return Binding::new(trigger, included, excluded);
+    }
+
+    fn control() -> Modifiers {
+        return Modifiers::from(false, true, false, false);
+    }
+
+    fn alt() -> Modifiers {
+        return Modifiers::from(false, false, true, false);
+    }
+
+    fn control_shift() -> Modifiers {
+        return Modifiers::from(true, true, false, false);
+    }
+
+    fn problems(bindings: &Vec<(BindingScope, Vec<Binding>, Action)>, sequence: Vec<Binding>, action: Action) -> Vec<String> {
+        return validate_sequence(bindings, &BindingScope::Global, &sequence, action).iter().map(|problem| problem.serialize()).collect();
+    }
+
+    #[test]
+    fn contradiction() {
+        let problems = problems(&Vec::new(), vec![binding(Key::K, control(), control())], Action::Copy);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("can never match"));
+    }
+
+    #[test]
+    fn duplicates() {
+        let bindings = vec![(BindingScope::Global, vec![binding(Key::C, control(), Modifiers::new())], Action::Copy)];
+
+        let same_action = problems(&bindings, vec![binding(Key::C, control(), Modifiers::new())], Action::Copy);
+        assert_eq!(same_action.len(), 1);
+        assert!(same_action[0].starts_with("duplicate binding"));
+
+        let other_action = problems(&bindings, vec![binding(Key::C, control(), Modifiers::new())], Action::Paste);
+        assert_eq!(other_action.len(), 1);
+        assert!(other_action[0].contains("is bound to both copy and paste"));
+    }
+
+    #[test]
+    fn other_scopes_are_ignored() {
+        let bindings = vec![(BindingScope::Editor, vec![binding(Key::C, control(), Modifiers::new())], Action::Copy)];
+        assert!(problems(&bindings, vec![binding(Key::C, control(), Modifiers::new())], Action::Paste).is_empty());
+    }
+
+    #[test]
+    fn prefix_shadows_sequence() {
+        let bindings = vec![(BindingScope::Global, vec![binding(Key::K, control(), Modifiers::new())], Action::Copy)];
+        let sequence = vec![binding(Key::K, control(), Modifiers::new()), binding(Key::B, control(), Modifiers::new())];
+
+        let problems = problems(&bindings, sequence, Action::CheatSheet);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("(copy) shadows"));
+    }
+
+    #[test]
+    fn specialization_is_allowed() {
+        let bindings = vec![(BindingScope::Global, vec![binding(Key::K, Modifiers::new(), Modifiers::new())], Action::Copy)];
+        assert!(problems(&bindings, vec![binding(Key::K, control(), Modifiers::new())], Action::Paste).is_empty());
+    }
+
+    #[test]
+    fn non_comparable_modifiers_do_not_conflict() {
+        let bindings = vec![(BindingScope::Global, vec![binding(Key::K, control(), Modifiers::new())], Action::Copy)];
+        assert!(problems(&bindings, vec![binding(Key::K, alt(), Modifiers::new())], Action::Paste).is_empty());
+
+        let sequence = vec![binding(Key::K, alt(), Modifiers::new()), binding(Key::B, alt(), Modifiers::new())];
+        assert!(problems(&bindings, sequence, Action::CheatSheet).is_empty());
+    }
+
+    #[test]
+    fn comparable_modifiers_conflict() {
+        let bindings = vec![(BindingScope::Global, vec![binding(Key::K, control(), alt())], Action::Copy)];
+
+        let problems = problems(&bindings, vec![binding(Key::K, control_shift(), Modifiers::new())], Action::Paste);
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("shadows"));
+    }
+}
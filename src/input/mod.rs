@@ -2,6 +2,11 @@ mod modifiers;
 mod binding;
 mod event;
 mod action;
+mod mouse;
+mod scope;
+mod problem;
+mod loader;
+mod writer;
 
 use seamonkey::*;
 use sfml::window::Key;
@@ -9,7 +14,12 @@ use sfml::window::Key;
 pub use self::modifiers::Modifiers;
 pub use self::binding::Binding;
 pub use self::event::KeyEvent;
-pub use self::action::Action;
+pub use self::action::{ Action, ActionInfo, ActionCategory, ACTIONS };
+pub use self::mouse::MouseEvent;
+pub use self::scope::BindingScope;
+pub use self::problem::BindingProblem;
+pub use self::loader::{ load_bindings, display_sequence, sequence_length };
+pub use self::writer::write_binding;
 
 pub fn is_modifier_key(key: Key) -> bool {
     match key {
@@ -124,3 +134,126 @@ pub fn key_from_literal(literal: &SharedString) -> Status<Key> {
         invalid => return error!(string!("invalid key {}", invalid)),
     }
 }
+
+pub fn literal_from_key(key: Key) -> &'static str {
+    match key {
+        Key::A => return "a",
+        Key::B => return "b",
+        Key::C => return "c",
+        Key::D => return "d",
+        Key::E => return "e",
+        Key::F => return "f",
+        Key::G => return "g",
+        Key::H => return "h",
+        Key::I => return "i",
+        Key::J => return "j",
+        Key::K => return "k",
+        Key::L => return "l",
+        Key::M => return "m",
+        Key::N => return "n",
+        Key::O => return "o",
+        Key::P => return "p",
+        Key::Q => return "q",
+        Key::R => return "r",
+        Key::S => return "s",
+        Key::T => return "t",
+        Key::U => return "u",
+        Key::V => return "v",
+        Key::W => return "w",
+        Key::X => return "x",
+        Key::Y => return "y",
+        Key::Z => return "z",
+        Key::NUM0 => return "0",
+        Key::NUM1 => return "1",
+        Key::NUM2 => return "2",
+        Key::NUM3 => return "3",
+        Key::NUM4 => return "4",
+        Key::NUM5 => return "5",
+        Key::NUM6 => return "6",
+        Key::NUM7 => return "7",
+        Key::NUM8 => return "8",
+        Key::NUM9 => return "9",
+        Key::ESCAPE => return "escape",
+        Key::LCONTROL => return "control",
+        Key::LSHIFT => return "shift",
+        Key::LALT => return "alt",
+        Key::LSYSTEM => return "system",
+        Key::MENU => return "menu",
+        Key::LBRACKET => return "left_bracket",
+        Key::RBRACKET => return "right_bracket",
+        Key::SEMICOLON => return "semicolon",
+        Key::COMMA => return "comma",
+        Key::PERIOD => return "period",
+        Key::QUOTE => return "quote",
+        Key::SLASH => return "slash",
+        Key::BACKSLASH => return "backslash",
+        Key::TILDE => return "tilde",
+        Key::EQUAL => return "equal",
+        Key::HYPHEN => return "hyphen",
+        Key::SPACE => return "space",
+        Key::ENTER => return "enter",
+        Key::BACKSPACE => return "backspace",
+        Key::TAB => return "tab",
+        Key::PAGEUP => return "page_up",
+        Key::PAGEDOWN => return "page_down",
+        Key::END => return "end",
+        Key::HOME => return "start",
+        Key::INSERT => return "insert",
+        Key::DELETE => return "delete",
+        Key::ADD => return "add",
+        Key::SUBTRACT => return "subtract",
+        Key::MULTIPLY => return "multiply",
+        Key::DIVIDE => return "divide",
+        Key::LEFT => return "left",
+        Key::RIGHT => return "right",
+        Key::UP => return "up",
+        Key::DOWN => return "down",
+        Key::NUMPAD0 => return "numpad_0",
+        Key::NUMPAD1 => return "numpad_1",
+        Key::NUMPAD2 => return "numpad_2",
+        Key::NUMPAD3 => return "numpad_3",
+        Key::NUMPAD4 => return "numpad_4",
+        Key::NUMPAD5 => return "numpad_5",
+        Key::NUMPAD6 => return "numpad_6",
+        Key::NUMPAD7 => return "numpad_7",
+        Key::NUMPAD8 => return "numpad_8",
+        Key::NUMPAD9 => return "numpad_9",
+        Key::F1 => return "f1",
+        Key::F2 => return "f2",
+        Key::F3 => return "f3",
+        Key::F4 => return "f4",
+        Key::F5 => return "f5",
+        Key::F6 => return "f6",
+        Key::F7 => return "f7",
+        Key::F8 => return "f8",
+        Key::F9 => return "f9",
+        Key::F10 => return "f10",
+        Key::F11 => return "f11",
+        Key::F12 => return "f12",
+        Key::F13 => return "f13",
+        Key::F14 => return "f14",
+        Key::F15 => return "f15",
+        Key::PAUSE => return "pause",
+        Key::RSHIFT => return "shift",
+        Key::RCONTROL => return "control",
+        Key::RALT => return "alt",
+        Key::RSYSTEM => return "system",
+        _other => return "unknown",
+    }
+}
+
+pub fn digit_from_key(key: Key) -> Option<usize> {
+    match key {
+        Key::NUM0 => return Some(0),
+        Key::NUM1 => return Some(1),
+        Key::NUM2 => return Some(2),
+        Key::NUM3 => return Some(3),
+        Key::NUM4 => return Some(4),
+        Key::NUM5 => return Some(5),
+        Key::NUM6 => return Some(6),
+        Key::NUM7 => return Some(7),
+        Key::NUM8 => return Some(8),
+        Key::NUM9 => return Some(9),
+        _other => return None,
+    }
+}
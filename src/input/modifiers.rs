@@ -1,3 +1,5 @@
+use seamonkey::*;
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Modifiers {
     pub shift: bool,
@@ -48,6 +50,36 @@ impl Modifiers {
         return count;
     }
 
+    pub fn intersects(&self, other: &Modifiers) -> bool {
+        return (self.shift && other.shift) || (self.control && other.control) || (self.alt && other.alt) || (self.system && other.system);
+    }
+
+    pub fn contains(&self, other: &Modifiers) -> bool {
+        return (self.shift || !other.shift) && (self.control || !other.control) && (self.alt || !other.alt) && (self.system || !other.system);
+    }
+
+    pub fn display(&self) -> SharedString {
+        let mut display = SharedString::new();
+
+        if self.control {
+            display.push_str(&SharedString::from("control "));
+        }
+
+        if self.alt {
+            display.push_str(&SharedString::from("alt "));
+        }
+
+        if self.shift {
+            display.push_str(&SharedString::from("shift "));
+        }
+
+        if self.system {
+            display.push_str(&SharedString::from("system "));
+        }
+
+        return display;
+    }
+
     pub fn matches_state(&self, modifiers: &Modifiers, state: bool) -> bool {
 
         if self.shift && modifiers.shift != state {
@@ -0,0 +1,10 @@
+use sfml::system::Vector2f;
+
+#[derive(Copy, Clone, Debug)]
+pub enum MouseEvent {
+    Pressed(Vector2f, usize, bool),
+    Moved(Vector2f),
+    Released,
+    Scrolled(f32),
+    ScrolledHorizontally(f32),
+}
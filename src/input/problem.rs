@@ -0,0 +1,28 @@
+use seamonkey::*;
+
+#[derive(Clone, Debug)]
+pub struct BindingProblem {
+    pub line: Option<usize>,
+    pub message: SharedString,
+}
+
+impl BindingProblem {
+
+    pub fn new(line: Option<usize>, message: SharedString) -> Self {
+        Self {
+            line: line,
+            message: message,
+        }
+    }
+
+    pub fn from_error(line: Option<usize>, error: Error) -> Self {
+        return Self::new(line, error.display(&None, &map!()));
+    }
+
+    pub fn display(&self) -> SharedString {
+        match self.line {
+            Some(line) => return format_shared!("line {}: {}", line, self.message),
+            None => return self.message.clone(),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use seamonkey::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BindingScope {
+    Global,
+    Editor,
+    Normal,
+    Combobox,
+    Open,
+    Filebuffers,
+    Notes,
+    Language,
+    Theme,
+    Replace,
+    Action,
+    Recent,
+    Find,
+    Confirm,
+    Syntax(SharedString),
+}
+
+impl BindingScope {
+
+    pub fn from_literal(literal: &SharedString) -> Status<Self> {
+        match literal.printable().as_str() {
+            "editor" => return success!(BindingScope::Editor),
+            "combobox" => return success!(BindingScope::Combobox),
+            "open" => return success!(BindingScope::Open),
+            "filebuffers" => return success!(BindingScope::Filebuffers),
+            "notes" => return success!(BindingScope::Notes),
+            "language" => return success!(BindingScope::Language),
+            "theme" => return success!(BindingScope::Theme),
+            "replace" => return success!(BindingScope::Replace),
+            "action" => return success!(BindingScope::Action),
+            "recent" => return success!(BindingScope::Recent),
+            "find" => return success!(BindingScope::Find),
+            "confirm" => return success!(BindingScope::Confirm),
+            invalid => return error!(string!("invalid binding scope {}", invalid)),
+        }
+    }
+
+    pub fn name(&self) -> SharedString {
+        match self {
+            BindingScope::Global => return SharedString::from("global"),
+            BindingScope::Editor => return SharedString::from("editor"),
+            BindingScope::Normal => return SharedString::from("normal"),
+            BindingScope::Combobox => return SharedString::from("combobox"),
+            BindingScope::Open => return SharedString::from("open"),
+            BindingScope::Filebuffers => return SharedString::from("filebuffers"),
+            BindingScope::Notes => return SharedString::from("notes"),
+            BindingScope::Language => return SharedString::from("language"),
+            BindingScope::Theme => return SharedString::from("theme"),
+            BindingScope::Replace => return SharedString::from("replace"),
+            BindingScope::Action => return SharedString::from("action"),
+            BindingScope::Recent => return SharedString::from("recent"),
+            BindingScope::Find => return SharedString::from("find"),
+            BindingScope::Confirm => return SharedString::from("confirm"),
+            BindingScope::Syntax(language) => return format_shared!("language {}", language),
+        }
+    }
+}
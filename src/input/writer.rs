@@ -0,0 +1,206 @@
+use seamonkey::*;
+
+use super::{ Action, Binding };
+
+struct Entry {
+    name: String,
+    line_start: usize,
+    list_start: usize,
+    list_end: usize,
+}
+
+fn line_start(text: &str, index: usize) -> usize {
+    return text[..index].rfind('\n').map(|position| position + 1).unwrap_or(0);
+}
+
+fn find_section(text: &str, section: &str) -> Option<usize> {
+    let mut offset = 0;
+
+    for line in text.split('\n') {
+        if line.trim_start().starts_with(section) {
+            return text[offset..].find('{').map(|position| offset + position + 1);
+        }
+        offset += line.len() + 1;
+    }
+
+    return None;
+}
+
+fn section_entries(text: &str, section_start: usize) -> (Vec<Entry>, usize) {
+    let mut entries = Vec::new();
+    let mut characters = text[section_start..].char_indices().map(|(index, character)| (index + section_start, character));
+    let mut bracket_depth = 0;
+    let mut brace_depth = 0;
+    let mut token_start = None;
+    let mut name = None;
+
+    while let Some((index, character)) = characters.next() {
+        match character {
+
+            '\'' | '"' => {
+                while let Some((_index, other)) = characters.next() {
+                    if other == character {
+                        break;
+                    }
+                }
+            },
+
+            '[' => {
+                if bracket_depth == 0 && brace_depth == 0 {
+                    if let Some(start) = token_start.take() {
+                        name = Some((text[start..index].trim().to_string(), line_start(text, start)));
+                    }
+
+                    if let Some((entry_name, entry_line_start)) = name.take() {
+                        entries.push(Entry {
+                            name: entry_name,
+                            line_start: entry_line_start,
+                            list_start: index,
+                            list_end: index,
+                        });
+                    }
+                }
+                bracket_depth += 1;
+            },
+
+            ']' => {
+                bracket_depth -= 1;
+
+                if bracket_depth == 0 && brace_depth == 0 {
+                    if let Some(entry) = entries.last_mut() {
+                        entry.list_end = index + 1;
+                    }
+                }
+            },
+
+            '{' => brace_depth += 1,
+
+            '}' => match brace_depth {
+                0 => return (entries, index),
+                _other => brace_depth -= 1,
+            },
+
+            character if character.is_whitespace() => {
+                if let Some(start) = token_start.take() {
+                    name = Some((text[start..index].to_string(), line_start(text, start)));
+                }
+            },
+
+            _other => {
+                if bracket_depth == 0 && brace_depth == 0 && token_start.is_none() {
+                    token_start = Some(index);
+                }
+            },
+        }
+    }
+
+    return (entries, text.len());
+}
+
+fn replace_binding(text: &str, action: Action, binding: &Binding) -> Status<String> {
+    let literal = action.info().literal;
+    let new_binding = format!("[ {} ]", binding.serialize());
+    let new_list = format!("[ {} ]", new_binding);
+
+    let section_start = expect!(find_section(text, "#bindings"), string!("missing #bindings section"));
+    let (entries, section_end) = section_entries(text, section_start);
+
+    if let Some(entry) = entries.iter().find(|entry| entry.name == literal) {
+        let list_close = entry.list_end - 1;
+        let mut new_text = text[..list_close].trim_end().to_string();
+        new_text.push_str(&format!(" {} ", new_binding));
+        new_text.push_str(&text[list_close..]);
+        return success!(new_text);
+    }
+
+    let (indentation, column, insert_position) = match entries.last() {
+        Some(last) => {
+            let first = &entries[0];
+            let indentation: String = text[first.line_start..].chars().take_while(|character| *character == ' ' || *character == '\t').collect();
+            let column = text[first.line_start..first.list_start].chars().count();
+            let insert_position = text[last.list_end..].find('\n').map(|position| last.list_end + position + 1).unwrap_or(section_end);
+            (indentation, column, insert_position)
+        },
+        None => (String::from("    "), 0, line_start(text, section_end)),
+    };
+
+    let insert_position = match insert_position < section_start {
+        true => section_end,
+        false => insert_position,
+    };
+
+    let mut line = format!("{}{} ", indentation, literal);
+    while line.chars().count() < column {
+        line.push(' ');
+    }
+    line.push_str(&new_list);
+    line.push('\n');
+
+    let mut new_text = text[..insert_position].to_string();
+    new_text.push_str(&line);
+    new_text.push_str(&text[insert_position..]);
+    return success!(new_text);
+}
+
+pub fn write_binding(bindings_file: &SharedString, action: Action, binding: &Binding) -> Status<()> {
+    let text = confirm!(read_file(bindings_file)).serialize();
+    let new_text = confirm!(replace_binding(&text, action, binding));
+    return write_file(bindings_file, &SharedString::from(new_text.as_str()));
+}
+
+#[cfg(test)]
+mod tests {
+
+    use sfml::window::Key;
+    use input::Modifiers;
+    use super::*;
+
+    fn control(trigger: Key) -> Binding {
+        return Binding::new(trigger, Modifiers::from(false, true, false, false), Modifiers::new());
+    }
+
+    fn replaced(text: &str, action: Action, binding: &Binding) -> String {
+        match replace_binding(text, action, binding) {
+            Status::Success(new_text) => return new_text,
+            Status::Error(_error) => panic!("failed to replace binding"),
+        }
+    }
+
+    #[test]
+    fn append_to_existing_entry() {
+        let text = "#bindings {\n\n    quit            [ [ control 'q' ] ]\n}\n";
+        let expected = "#bindings {\n\n    quit            [ [ control 'q' ] [ control 'w' ] ]\n}\n";
+        assert_eq!(replaced(text, Action::Quit, &control(Key::W)), expected);
+    }
+
+    #[test]
+    fn insert_new_entry_aligned() {
+        let text = "#bindings {\n\n    quit            [ [ control 'q' ] ]\n}\n\n#normal {\n\n    undo [ [ 'u' ] ]\n}\n";
+        let expected = "#bindings {\n\n    quit            [ [ control 'q' ] ]\n    copy            [ [ control 'c' ] ]\n}\n\n#normal {\n\n    undo [ [ 'u' ] ]\n}\n";
+        assert_eq!(replaced(text, Action::Copy, &control(Key::C)), expected);
+    }
+
+    #[test]
+    fn insert_into_empty_section() {
+        let text = "#bindings {\n}\n";
+        let expected = "#bindings {\n    copy [ [ control 'c' ] ]\n}\n";
+        assert_eq!(replaced(text, Action::Copy, &control(Key::C)), expected);
+    }
+
+    #[test]
+    fn quoted_brackets_are_skipped() {
+        let text = "#bindings {\n\n    indent          [ [ control ']' ] ]\n    quit            [ [ control '[' ] ]\n}\n";
+        let expected = "#bindings {\n\n    indent          [ [ control ']' ] [ control 'w' ] ]\n    quit            [ [ control '[' ] ]\n}\n";
+        assert_eq!(replaced(text, Action::Indent, &control(Key::W)), expected);
+    }
+
+    #[test]
+    fn missing_bindings_section() {
+        let text = "#normal {\n\n    undo [ [ 'u' ] ]\n}\n";
+
+        match replace_binding(text, Action::Copy, &control(Key::C)) {
+            Status::Success(_new_text) => panic!("expected an error"),
+            Status::Error(_error) => { },
+        }
+    }
+}
@@ -1,11 +1,15 @@
 use seamonkey::*;
 
 use sfml::SfBox;
-use sfml::window::Key;
 use sfml::graphics::Font;
 
+use std::fs::read_dir;
+
 use input::*;
 use input::Action;
+use system::code_point;
+
+const FALLBACK_FONTS_DIRECTORY: &'static str = "/home/.config/poet/fonts/fallback/";
 
 const SMALLEST_FONT_SIZE: usize = 5;
 const BIGGEST_FONT_SIZE: usize = 50;
@@ -16,83 +20,65 @@ const ANTIALIASING_MAX: usize = 8;
 pub struct InterfaceContext {
     pub font_size: usize,
     pub font: SfBox<Font>,
-    pub bindings: Vec<(Binding, Action)>,
+    pub fallback_fonts: Vec<SfBox<Font>>,
+    pub bindings: Vec<(BindingScope, Vec<Binding>, Action)>,
+    pub count_modifiers: Modifiers,
+    pub binding_problems: Vec<BindingProblem>,
+    pub bindings_file: SharedString,
     pub selection_gap: usize,
     pub line_spacing: f32,
     pub character_spacing: f32,
     pub antialiasing_level: usize,
 }
 
+fn sequence_matches(sequence: &[Binding], key_events: &[KeyEvent]) -> bool {
+    return sequence.iter().zip(key_events.iter()).all(|(binding, key_event)| binding.matches(&key_event.trigger, &key_event.modifiers));
+}
+
+fn scope_accepts(scope: &BindingScope, key_events: &[KeyEvent]) -> bool {
+    match scope {
+        BindingScope::Normal => return key_events.iter().all(|key_event| !key_event.modifiers.control && !key_event.modifiers.alt && !key_event.modifiers.system),
+        _other => return true,
+    }
+}
+
+fn matching_actions(bindings: &[(BindingScope, Vec<Binding>, Action)], scopes: &[BindingScope], key_events: &[KeyEvent]) -> Vec<Action> {
+    let mut actions = Vec::new();
+
+    for scope in scopes.iter().filter(|scope| scope_accepts(scope, key_events)) {
+        for (binding_scope, sequence, action) in bindings.iter() {
+            if binding_scope == scope && sequence.len() == key_events.len() && sequence_matches(sequence, key_events) {
+                if !actions.contains(action) {
+                    actions.push(*action);
+                }
+            }
+        }
+    }
+    return actions;
+}
+
+fn sequence_prefix(bindings: &[(BindingScope, Vec<Binding>, Action)], scopes: &[BindingScope], key_events: &[KeyEvent]) -> bool {
+    return scopes.iter().filter(|scope| scope_accepts(scope, key_events)).any(|scope| bindings.iter().any(|(binding_scope, sequence, _)| binding_scope == scope && sequence.len() > key_events.len() && sequence_matches(sequence, key_events)));
+}
+
 impl InterfaceContext {
 
     pub fn temp() -> Status<Self> {
 
         let font = Font::from_file("/home/.config/poet/fonts/monaco.ttf").expect("failed to load font");
 
+        let fallback_fonts = Self::load_fallback_fonts();
         let bindings_file = format_shared!("/home/.config/poet/bindings.data");
-        let bindings_data = confirm!(read_map(&bindings_file));
-
-        let bindings_entry = confirm!(bindings_data.index(&keyword!("bindings"))).unwrap();
-        let bindings_entry = unpack_map!(&bindings_entry);
-
-        let mut bindings = Vec::new();
-
-        for (key, value) in bindings_entry.iter() {
-            let action = confirm!(Action::from_literal(&unpack_literal!(key)));
-
-            let bindings_list = unpack_list!(value);
-            for binding in bindings_list.iter() {
-
-                let mut trigger = None;
-                let mut included = Modifiers::new();
-                let mut excluded = Modifiers::new();
-
-                let binding_keys_list = unpack_list!(binding);
-                for binding_key in binding_keys_list.iter() {
-
-                    let key = confirm!(key_from_literal(&unpack_literal!(binding_key)));
-
-                    if binding_key.is_keyword() {
-                        if is_modifier_key(key) {
-                            match key {
-                                Key::LSHIFT => excluded.shift = true,
-                                Key::LCONTROL => excluded.control = true,
-                                Key::LALT => excluded.alt = true,
-                                Key::LSYSTEM => excluded.system = true,
-                                _other => panic!(),
-                            }
-                        } else {
-                            return error!(string!("only modifiers can be excluded in bindings"));
-                        }
-
-                    } else {
-                        if is_modifier_key(key) {
-                            match key {
-                                Key::LSHIFT => included.shift = true,
-                                Key::LCONTROL => included.control = true,
-                                Key::LALT => included.alt = true,
-                                Key::LSYSTEM => included.system = true,
-                                _other => panic!(),
-                            }
-                        } else {
-                            trigger = Some(key);
-                        }
-                    }
-                }
-
-                let trigger = expect!(trigger, string!("keybinding must have a trigger"));
-                let new_binding = Binding::new(trigger, included, excluded);
-                match bindings.iter().position(|(binding, _): &(Binding, Action)| binding.length() <= new_binding.length()) {
-                    Some(index) => bindings.insert(index, (new_binding, action)),
-                    None => bindings.push((new_binding, action)),
-                }
-            }
-        }
+        let (bindings, count_modifiers, binding_problems) = load_bindings(&bindings_file);
 
         return success!(Self {
             font_size: 14,
             font: font,
+            fallback_fonts: fallback_fonts,
             bindings: bindings,
+            count_modifiers: count_modifiers,
+            binding_problems: binding_problems,
+            bindings_file: bindings_file,
             selection_gap: 8,
             line_spacing: 1.4,
             character_spacing: 0.625,
@@ -100,16 +86,56 @@ impl InterfaceContext {
         });
     }
 
-    pub fn get_matching_actions(&self, key_event: &KeyEvent) -> Vec<Action> {
-        let mut actions = Vec::new();
-        for (binding, action) in self.bindings.iter() {
-            if binding.matches(&key_event.trigger, &key_event.modifiers) {
-                if !actions.contains(action) {
-                    actions.push(*action);
-                }
+    fn load_fallback_fonts() -> Vec<SfBox<Font>> {
+        let mut font_files = Vec::new();
+
+        if let Ok(entries) = read_dir(FALLBACK_FONTS_DIRECTORY) {
+            for entry in entries.filter_map(|entry| entry.ok()) {
+                font_files.push(entry.path());
+            }
+        }
+
+        font_files.sort();
+        return font_files.iter().filter_map(|path| path.to_str().and_then(Font::from_file)).collect();
+    }
+
+    pub fn font_for(&self, character: Character) -> &Font {
+        let code_point = code_point(character);
+
+        if self.font.has_glyph(code_point) {
+            return &self.font;
+        }
+
+        match self.fallback_fonts.iter().find(|font| font.has_glyph(code_point)) {
+            Some(font) => return font,
+            None => return &self.font,
+        }
+    }
+
+    pub fn get_matching_actions(&self, scopes: &[BindingScope], key_events: &[KeyEvent]) -> Vec<Action> {
+        return matching_actions(&self.bindings, scopes, key_events);
+    }
+
+    pub fn display_bindings(&self, action: Action, scopes: &[BindingScope]) -> SharedString {
+        let mut display = SharedString::new();
+
+        for (scope, sequence, _) in self.bindings.iter().filter(|(scope, _, other)| *other == action && scopes.contains(scope)) {
+            if !display.is_empty() {
+                display.push_str(&SharedString::from(" | "));
+            }
+
+            display.push_str(&display_sequence(sequence));
+
+            if *scope != BindingScope::Global {
+                display.push_str(&format_shared!(" ({})", scope.name()));
             }
         }
-        return actions;
+
+        return display;
+    }
+
+    pub fn is_sequence_prefix(&self, scopes: &[BindingScope], key_events: &[KeyEvent]) -> bool {
+        return sequence_prefix(&self.bindings, scopes, key_events);
     }
 
     pub fn zoom_in(&mut self) -> bool {
@@ -144,3 +170,57 @@ impl InterfaceContext {
         return false;
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use sfml::window::Key;
+    use super::*;
+
+    fn binding(trigger: Key, control: bool) -> Binding {
+        return Binding::new(trigger, Modifiers::from(false, control, false, false), Modifiers::new());
+    }
+
+    fn key_event(trigger: Key, control: bool) -> KeyEvent {
+        return KeyEvent::new(trigger, Modifiers::from(false, control, false, false));
+    }
+
+    #[test]
+    fn actions_fall_through_scopes_in_order() {
+        let bindings = vec![
+            (BindingScope::Global, vec![binding(Key::O, true)], Action::Open),
+            (BindingScope::Open, vec![binding(Key::O, true)], Action::Abort),
+        ];
+
+        let scopes = [BindingScope::Open, BindingScope::Global];
+        let actions = matching_actions(&bindings, &scopes, &[key_event(Key::O, true)]);
+        assert_eq!(actions, vec![Action::Abort, Action::Open]);
+
+        let scopes = [BindingScope::Editor, BindingScope::Global];
+        let actions = matching_actions(&bindings, &scopes, &[key_event(Key::O, true)]);
+        assert_eq!(actions, vec![Action::Open]);
+    }
+
+    #[test]
+    fn normal_scope_ignores_modified_keys() {
+        let bindings = vec![
+            (BindingScope::Normal, vec![binding(Key::J, false)], Action::Down),
+            (BindingScope::Global, vec![binding(Key::J, true)], Action::Quit),
+        ];
+
+        let scopes = [BindingScope::Normal, BindingScope::Global];
+        assert_eq!(matching_actions(&bindings, &scopes, &[key_event(Key::J, false)]), vec![Action::Down]);
+        assert_eq!(matching_actions(&bindings, &scopes, &[key_event(Key::J, true)]), vec![Action::Quit]);
+    }
+
+    #[test]
+    fn sequence_prefix_in_any_scope() {
+        let bindings = vec![
+            (BindingScope::Global, vec![binding(Key::K, true), binding(Key::B, true)], Action::CheatSheet),
+        ];
+
+        let scopes = [BindingScope::Combobox, BindingScope::Global];
+        assert!(sequence_prefix(&bindings, &scopes, &[key_event(Key::K, true)]));
+        assert!(!sequence_prefix(&bindings, &scopes, &[key_event(Key::B, true)]));
+    }
+}
@@ -1,5 +1,6 @@
 mod context;
 mod vector;
+mod repeat;
 
 use seamonkey::*;
 
@@ -7,12 +8,14 @@ use seamonkey::*;
 use debug::*;
 
 use std::cmp::{ min, max };
+use std::collections::HashMap;
 use sfml::graphics::*;
 use sfml::system::Vector2f;
 
-use input::Action;
+use input::{ Action, BindingScope, KeyEvent, MouseEvent, write_binding };
 use themes::InterfaceTheme;
-use filebuffer::Filebuffer;
+use filebuffer::{ Filebuffer, FileFormat, read_encoded_file, write_encoded_file };
+use selection::Selection;
 use elements::*;
 use dialogues::*;
 use managers::*;
@@ -20,6 +23,8 @@ use managers::*;
 pub use self::context::InterfaceContext;
 pub use self::vector::Vector4f;
 
+use self::repeat::{ LastEdit, repeat_steps };
+
 macro_rules! handle_return {
     ($expression: expr) => ({
         $expression;
@@ -39,9 +44,20 @@ macro_rules! confirm_or_error {
     })
 }
 
+fn convert_format(filebuffer: &mut Filebuffer, convert: impl Fn(&mut FileFormat)) {
+    let mut format = filebuffer.get_format();
+    convert(&mut format);
+    filebuffer.set_format(format);
+}
+
+fn is_unnamed_buffer(file_name: &SharedString) -> bool {
+    return file_name[0] == Character::from_char('<');
+}
+
 pub struct Interface {
     file_name: SharedString,
     textbuffer: Textbuffer,
+    buffer_positions: HashMap<String, BufferPosition>,
     dialogue_mode: DialogueMode,
     open_file_dialogue: OpenDialogue,
     loaded_buffers_dialogue: FilebuffersDialogue,
@@ -50,6 +66,17 @@ pub struct Interface {
     set_theme_dialogue: ThemeDialogue,
     find_replace_dialogue: ReplaceDialogue,
     action_dialogue: ActionDialogue,
+    recent_files_dialogue: RecentDialogue,
+    find_file_dialogue: FindDialogue,
+    close_buffer_dialogue: ConfirmDialogue,
+    binding_report_dialogue: BindingReportDialogue,
+    cheat_sheet_dialogue: CheatSheetDialogue,
+    edit_binding_dialogue: BindingDialogue,
+    name_macro_dialogue: MacroDialogue,
+    count_dialogue: CountDialogue,
+    recording: Option<Vec<MacroStep>>,
+    pending_count: Option<usize>,
+    last_edit: LastEdit,
     error_message: Option<SharedString>,
     popup: Popup,
 }
@@ -65,6 +92,7 @@ impl Interface {
         success!(Self {
             file_name: SharedString::from(&new_name),
             textbuffer: Textbuffer::new(window_id, Vector2f::new(400., 50.), Vector2f::new(0., 0.), '\n'),
+            buffer_positions: HashMap::new(),
             dialogue_mode: DialogueMode::None,
             open_file_dialogue: OpenDialogue::new(language_manager),
             loaded_buffers_dialogue: FilebuffersDialogue::new(language_manager),
@@ -73,6 +101,17 @@ impl Interface {
             set_theme_dialogue: ThemeDialogue::new(language_manager),
             find_replace_dialogue: ReplaceDialogue::new(language_manager),
             action_dialogue: ActionDialogue::new(language_manager),
+            recent_files_dialogue: RecentDialogue::new(language_manager),
+            find_file_dialogue: FindDialogue::new(language_manager),
+            close_buffer_dialogue: ConfirmDialogue::new(language_manager, "discard unsaved changes", "discard changes"),
+            binding_report_dialogue: BindingReportDialogue::new(language_manager),
+            cheat_sheet_dialogue: CheatSheetDialogue::new(language_manager),
+            edit_binding_dialogue: BindingDialogue::new(language_manager),
+            name_macro_dialogue: MacroDialogue::new(language_manager),
+            count_dialogue: CountDialogue::new(language_manager),
+            recording: None,
+            pending_count: None,
+            last_edit: LastEdit::new(),
             error_message: None,
             popup: Popup::new(),
         })
@@ -92,6 +131,7 @@ impl Interface {
         let position = Vector2f::new(left_position, top_position);
 
         let filebuffer = filebuffer_manager.get(&self.file_name.serialize());
+        self.textbuffer.set_columns((dialogue_size.x / character_scaling) as usize, textbuffer_context.soft_wrap);
         self.textbuffer.update_layout(interface_context, textbuffer_context, filebuffer, size);
 
         self.open_file_dialogue.update_layout(interface_context, &theme.dialogue_theme, dialogue_size, position);
@@ -101,15 +141,25 @@ impl Interface {
         self.set_theme_dialogue.update_layout(interface_context, &theme.dialogue_theme, dialogue_size, position);
         self.find_replace_dialogue.update_layout(interface_context, &theme.dialogue_theme, dialogue_size, position);
         self.action_dialogue.update_layout(interface_context, &theme.dialogue_theme, dialogue_size, position);
+        self.recent_files_dialogue.update_layout(interface_context, &theme.dialogue_theme, dialogue_size, position);
+        self.find_file_dialogue.update_layout(interface_context, &theme.dialogue_theme, dialogue_size, position);
+        self.close_buffer_dialogue.update_layout(interface_context, &theme.dialogue_theme, dialogue_size, position);
+        self.binding_report_dialogue.update_layout(interface_context, &theme.dialogue_theme, dialogue_size, position);
+        self.cheat_sheet_dialogue.update_layout(interface_context, &theme.dialogue_theme, dialogue_size, position);
+        self.edit_binding_dialogue.update_layout(interface_context, &theme.dialogue_theme, dialogue_size, position);
+        self.name_macro_dialogue.update_layout(interface_context, &theme.dialogue_theme, dialogue_size, position);
+        self.count_dialogue.update_layout(interface_context, &theme.dialogue_theme, dialogue_size, position);
 
         self.popup.update_layout(dialogue_size, position);
     }
 
-    pub fn new_file(&mut self, filebuffer_manager: &mut FilebufferManager, language_manager: &mut LanguageManager) -> Status<()> {
+    pub fn new_file(&mut self, filebuffer_manager: &mut FilebufferManager, language_manager: &mut LanguageManager, position_manager: &mut PositionManager) -> Status<()> {
 
         #[cfg(feature = "debug")]
         let timer = Timer::new("new file");
 
+        self.store_position(position_manager);
+
         let language = SharedString::from("none");
         let new_name = format!("<unnamed {}>", filebuffer_manager.next_index());
 
@@ -129,35 +179,229 @@ impl Interface {
         return success!(());
     }
 
-    pub fn save_file(&mut self, filebuffer_manager: &mut FilebufferManager) {
+    fn is_unnamed(&self) -> bool {
+        return is_unnamed_buffer(&self.file_name);
+    }
+
+    pub fn store_position(&mut self, position_manager: &mut PositionManager) {
+        let position = self.textbuffer.get_position();
+        let string_file_name = self.file_name.serialize();
+
+        if !self.is_unnamed() {
+            position_manager.set(string_file_name.clone(), position.clone());
+        }
+
+        self.buffer_positions.insert(string_file_name, position);
+    }
+
+    pub fn save_file(&mut self, filebuffer_manager: &mut FilebufferManager, position_manager: &mut PositionManager, recent_manager: &mut RecentManager) {
+        let file_name = self.file_name.clone();
+        self.save_buffer(filebuffer_manager, position_manager, recent_manager, file_name);
+    }
+
+    fn save_buffer(&mut self, filebuffer_manager: &mut FilebufferManager, position_manager: &mut PositionManager, recent_manager: &mut RecentManager, file_name: SharedString) {
 
         #[cfg(feature = "debug")]
         let timer = Timer::new("save file");
 
-        if self.file_name[0] == Character::from_char('<') {
+        if is_unnamed_buffer(&file_name) {
             self.set_error_state(Error::Message(string!("cannot save file without file name (yet)")));
             return;
         }
 
-        let current_text = filebuffer_manager.get(&self.file_name.serialize()).get_text();
-        if let Status::Error(error) = write_file(&self.file_name, &current_text) {
+        let string_file_name = file_name.serialize();
+        let filebuffer = filebuffer_manager.get_mut(&string_file_name);
+
+        if let Status::Error(error) = write_encoded_file(&file_name, &filebuffer.get_text(), &filebuffer.get_format()) {
+            self.set_error_state(error);
+            return;
+        }
+
+        filebuffer.mark_saved();
+
+        if let Status::Error(error) = recent_manager.touch(&string_file_name) {
             self.set_error_state(error);
         }
 
+        if file_name == self.file_name {
+            self.store_position(position_manager);
+        }
+
         #[cfg(feature = "debug")]
         timer.stop();
     }
 
-    pub fn scroll_up(&mut self, textbuffer_context: &TextbufferContext) {
-        self.textbuffer.scroll_up(textbuffer_context);
+    fn close_buffer(&mut self, textbuffer_context: &TextbufferContext, filebuffer_manager: &mut FilebufferManager, language_manager: &mut LanguageManager, position_manager: &mut PositionManager, file_name: SharedString, discard: bool) {
+        let string_file_name = file_name.serialize();
+
+        if !discard && filebuffer_manager.get(&string_file_name).is_modified() {
+            self.dialogue_mode = self.close_buffer_dialogue.open(language_manager, file_name);
+            return;
+        }
+
+        if file_name == self.file_name {
+            self.store_position(position_manager);
+        }
+
+        filebuffer_manager.remove(&string_file_name);
+        self.validate_buffer(textbuffer_context, filebuffer_manager, language_manager, position_manager);
     }
 
-    pub fn scroll_down(&mut self, filebuffer_manager: &FilebufferManager, textbuffer_context: &TextbufferContext) {
-        let filebuffer = filebuffer_manager.get(&self.file_name.serialize());
-        self.textbuffer.scroll_down(textbuffer_context, filebuffer);
+    pub fn validate_buffer(&mut self, textbuffer_context: &TextbufferContext, filebuffer_manager: &mut FilebufferManager, language_manager: &mut LanguageManager, position_manager: &mut PositionManager) -> bool {
+        if filebuffer_manager.contains(&self.file_name.serialize()) {
+            return false;
+        }
+
+        let next_file_name = filebuffer_manager.iter().map(|(file_name, _)| file_name.clone()).next();
+
+        match next_file_name {
+            Some(file_name) => self.switch_buffer(textbuffer_context, filebuffer_manager, position_manager, SharedString::from(file_name.as_str())),
+            None => { self.new_file(filebuffer_manager, language_manager, position_manager); },
+        }
+
+        return true;
     }
 
-    pub fn open_buffer(&mut self, filebuffer_manager: &mut FilebufferManager, language_manager: &mut LanguageManager, file_name: SharedString) {
+    fn switch_buffer(&mut self, textbuffer_context: &TextbufferContext, filebuffer_manager: &mut FilebufferManager, position_manager: &PositionManager, file_name: SharedString) {
+        let string_file_name = file_name.serialize();
+        let position = match self.buffer_positions.get(&string_file_name) {
+            Some(position) => Some(position.clone()),
+            None => position_manager.get(&string_file_name).cloned(),
+        };
+
+        let filebuffer = filebuffer_manager.get_mut(&string_file_name);
+        match position {
+            Some(position) => self.textbuffer.restore_position(textbuffer_context, filebuffer, &position),
+            None => self.textbuffer.reset(filebuffer),
+        }
+
+        self.file_name = file_name;
+    }
+
+    fn handle_textbuffer_mouse(&mut self, interface_context: &InterfaceContext, textbuffer_context: &TextbufferContext, theme: &InterfaceTheme, filebuffer_manager: &mut FilebufferManager, mouse_event: MouseEvent) -> bool {
+        let filebuffer = filebuffer_manager.get_mut(&self.file_name.serialize());
+
+        match mouse_event {
+
+            MouseEvent::Pressed(position, click_count, add_selection) => {
+                self.error_message = None;
+                self.textbuffer.press_mouse(interface_context, textbuffer_context, &theme.textbuffer_theme, filebuffer, position, click_count, add_selection);
+                return true;
+            },
+
+            MouseEvent::Moved(position) => return self.textbuffer.drag_mouse(interface_context, textbuffer_context, &theme.textbuffer_theme, filebuffer, position),
+
+            MouseEvent::Released => {
+                self.textbuffer.release_mouse();
+                return false;
+            },
+
+            MouseEvent::Scrolled(delta) => {
+                match delta > 0.0 {
+                    true => self.textbuffer.scroll_up(textbuffer_context),
+                    false => self.textbuffer.scroll_down(textbuffer_context, filebuffer),
+                }
+                return true;
+            },
+
+            MouseEvent::ScrolledHorizontally(delta) => {
+                match delta > 0.0 {
+                    true => self.textbuffer.scroll_left(textbuffer_context),
+                    false => self.textbuffer.scroll_right(textbuffer_context, filebuffer),
+                }
+                return true;
+            },
+        }
+    }
+
+    fn dialogue_hovered(&self) -> Option<usize> {
+        match self.dialogue_mode {
+
+            DialogueMode::None => return None,
+
+            DialogueMode::Open => return self.open_file_dialogue.get_hovered(),
+
+            DialogueMode::Filebuffers => return self.loaded_buffers_dialogue.get_hovered(),
+
+            DialogueMode::Notes => return self.notes_dialogue.get_hovered(),
+
+            DialogueMode::Language => return self.set_language_dialogue.get_hovered(),
+
+            DialogueMode::Theme => return self.set_theme_dialogue.get_hovered(),
+
+            DialogueMode::Replace(..) => return None,
+
+            DialogueMode::Action => return self.action_dialogue.get_hovered(),
+
+            DialogueMode::Recent => return self.recent_files_dialogue.get_hovered(),
+
+            DialogueMode::Find => return self.find_file_dialogue.get_hovered(),
+
+            DialogueMode::CloseBuffer(..) => return self.close_buffer_dialogue.get_hovered(),
+
+            DialogueMode::BindingReport => return self.binding_report_dialogue.get_hovered(),
+
+            DialogueMode::CheatSheet => return self.cheat_sheet_dialogue.get_hovered(),
+
+            DialogueMode::EditBinding => return self.edit_binding_dialogue.get_hovered(),
+
+            DialogueMode::NameMacro => return self.name_macro_dialogue.get_hovered(),
+
+            DialogueMode::Count => return None,
+        }
+    }
+
+    pub fn handle_mouse(&mut self, interface_context: &InterfaceContext, textbuffer_context: &TextbufferContext, theme: &InterfaceTheme, filebuffer_manager: &mut FilebufferManager, language_manager: &mut LanguageManager, position_manager: &mut PositionManager, recent_manager: &mut RecentManager, macro_manager: &mut MacroManager, mouse_event: MouseEvent, theme_name: &mut SharedString) -> bool {
+        let previous_hovered = self.dialogue_hovered();
+        let moved = match mouse_event {
+            MouseEvent::Moved(..) => true,
+            _other => false,
+        };
+
+        let action = match self.dialogue_mode {
+
+            DialogueMode::None => return self.handle_textbuffer_mouse(interface_context, textbuffer_context, theme, filebuffer_manager, mouse_event),
+
+            DialogueMode::Open => self.open_file_dialogue.handle_mouse(language_manager, mouse_event),
+
+            DialogueMode::Filebuffers => self.loaded_buffers_dialogue.handle_mouse(language_manager, mouse_event),
+
+            DialogueMode::Notes => self.notes_dialogue.handle_mouse(language_manager, mouse_event),
+
+            DialogueMode::Language => self.set_language_dialogue.handle_mouse(language_manager, mouse_event),
+
+            DialogueMode::Theme => self.set_theme_dialogue.handle_mouse(language_manager, mouse_event),
+
+            DialogueMode::Replace(..) => self.find_replace_dialogue.handle_mouse(mouse_event),
+
+            DialogueMode::Action => self.action_dialogue.handle_mouse(language_manager, mouse_event),
+
+            DialogueMode::Recent => self.recent_files_dialogue.handle_mouse(language_manager, mouse_event),
+
+            DialogueMode::Find => self.find_file_dialogue.handle_mouse(language_manager, mouse_event),
+
+            DialogueMode::CloseBuffer(..) => self.close_buffer_dialogue.handle_mouse(language_manager, mouse_event),
+
+            DialogueMode::BindingReport => self.binding_report_dialogue.handle_mouse(language_manager, mouse_event),
+
+            DialogueMode::CheatSheet => self.cheat_sheet_dialogue.handle_mouse(language_manager, mouse_event),
+
+            DialogueMode::EditBinding => self.edit_binding_dialogue.handle_mouse(language_manager, mouse_event),
+
+            DialogueMode::NameMacro => self.name_macro_dialogue.handle_mouse(language_manager, mouse_event),
+
+            DialogueMode::Count => self.count_dialogue.handle_mouse(mouse_event),
+        };
+
+        if let Some(action) = action {
+            self.handle_action(interface_context, textbuffer_context, filebuffer_manager, language_manager, position_manager, recent_manager, macro_manager, action, theme_name);
+            return true;
+        }
+
+        return !moved || self.dialogue_hovered() != previous_hovered;
+    }
+
+    pub fn open_buffer(&mut self, textbuffer_context: &TextbufferContext, filebuffer_manager: &mut FilebufferManager, language_manager: &mut LanguageManager, position_manager: &mut PositionManager, recent_manager: &mut RecentManager, file_name: SharedString) {
         let string_file_name = file_name.serialize();
 
         if file_name != self.file_name {
@@ -171,13 +415,8 @@ impl Interface {
                 let read_timer = Timer::new_dynamic(format!("read file {}{}{}", magenta(), string_file_name, none()));
 
                 let language = SharedString::from("none");
-                let mut text = display!(read_file(&file_name));
-
-                if text.is_empty() || !text[text.len() - 1].is_newline() {
-                    text.push(Character::from_char('\n'));
-                }
-
-                let filebuffer = Filebuffer::new(language_manager, language, text.clone());
+                let (text, format) = display!(read_encoded_file(&file_name));
+                let filebuffer = Filebuffer::new(language_manager, language, text).with_format(format);
                 filebuffer_manager.insert(string_file_name.clone(), filebuffer);
 
                 #[cfg(feature = "debug")]
@@ -186,26 +425,73 @@ impl Interface {
 
             let current_file_name = self.file_name.serialize();
             let current_file_length = filebuffer_manager.get(&current_file_name).length();
+            self.store_position(position_manager);
 
-            if self.file_name[0] == Character::from_char('<') && current_file_length <= 1 {
+            if self.is_unnamed() && current_file_length <= 1 {
                 filebuffer_manager.remove(&current_file_name);
+                self.buffer_positions.remove(&current_file_name);
             }
 
-            let filebuffer = filebuffer_manager.get_mut(&string_file_name);
-            self.textbuffer.reset(filebuffer);
-            self.file_name = file_name;
+            self.switch_buffer(textbuffer_context, filebuffer_manager, position_manager, file_name);
+
+            if !self.is_unnamed() {
+                if let Status::Error(error) = recent_manager.touch(&string_file_name) {
+                    self.set_error_state(error);
+                }
+            }
 
             #[cfg(feature = "debug")]
             timer.stop();
         }
     }
 
+    pub fn poll_dialogues(&mut self) -> bool {
+        match self.dialogue_mode {
+            DialogueMode::Find => return self.find_file_dialogue.poll(),
+            _other => {
+                // the dialogue can also be replaced without an abort, so stop the walker here too
+                self.find_file_dialogue.close();
+                return false;
+            },
+        }
+    }
+
+    pub fn binding_scopes(&self, textbuffer_context: &TextbufferContext, filebuffer_manager: &FilebufferManager) -> Vec<BindingScope> {
+        let mut scopes = match &self.dialogue_mode {
+            DialogueMode::None => {
+                let language = filebuffer_manager.get(&self.file_name.serialize()).get_language();
+                match textbuffer_context.modal && self.textbuffer.get_edit_mode() == EditMode::Normal {
+                    true => vec![BindingScope::Normal, BindingScope::Syntax(language), BindingScope::Editor],
+                    false => vec![BindingScope::Syntax(language), BindingScope::Editor],
+                }
+            },
+            DialogueMode::Open => vec![BindingScope::Open],
+            DialogueMode::Filebuffers => vec![BindingScope::Filebuffers, BindingScope::Combobox],
+            DialogueMode::Notes => vec![BindingScope::Notes, BindingScope::Combobox],
+            DialogueMode::Language => vec![BindingScope::Language, BindingScope::Combobox],
+            DialogueMode::Theme => vec![BindingScope::Theme, BindingScope::Combobox],
+            DialogueMode::Replace(..) => vec![BindingScope::Replace],
+            DialogueMode::Action => vec![BindingScope::Action, BindingScope::Combobox],
+            DialogueMode::Recent => vec![BindingScope::Recent, BindingScope::Combobox],
+            DialogueMode::Find => vec![BindingScope::Find, BindingScope::Combobox],
+            DialogueMode::CloseBuffer(..) => vec![BindingScope::Confirm, BindingScope::Combobox],
+            DialogueMode::BindingReport => vec![BindingScope::Combobox],
+            DialogueMode::CheatSheet => vec![BindingScope::Combobox],
+            DialogueMode::EditBinding => vec![BindingScope::Combobox],
+            DialogueMode::NameMacro => vec![BindingScope::Combobox],
+            DialogueMode::Count => vec![],
+        };
+
+        scopes.push(BindingScope::Global);
+        return scopes;
+    }
+
     pub fn history_catch_up(&mut self, textbuffer_context: &TextbufferContext, filebuffer_manager: &mut FilebufferManager) -> bool {
         let filebuffer = filebuffer_manager.get_mut(&self.file_name.serialize());
         return self.textbuffer.history_catch_up(textbuffer_context, filebuffer);
     }
 
-    pub fn handle_action(&mut self, interface_context: &InterfaceContext, textbuffer_context: &TextbufferContext, filebuffer_manager: &mut FilebufferManager, language_manager: &mut LanguageManager, action: Action, theme_name: &mut SharedString) -> Option<Action> {
+    pub fn handle_action(&mut self, interface_context: &InterfaceContext, textbuffer_context: &TextbufferContext, filebuffer_manager: &mut FilebufferManager, language_manager: &mut LanguageManager, position_manager: &mut PositionManager, recent_manager: &mut RecentManager, macro_manager: &mut MacroManager, action: Action, theme_name: &mut SharedString) -> Option<Action> {
 
         if self.error_message.is_some() {
             self.error_message = None;
@@ -215,7 +501,35 @@ impl Interface {
 
         let unhandled_action = match self.dialogue_mode.clone() {
 
-            DialogueMode::None => self.textbuffer.handle_action(textbuffer_context, language_manager, filebuffer, action),
+            DialogueMode::None => {
+                let history_index = filebuffer.get_history_index();
+                let mut unhandled_action = self.textbuffer.handle_action(textbuffer_context, language_manager, filebuffer, action);
+
+                if unhandled_action.is_none() {
+                    let count = self.take_count();
+                    self.record_step(MacroStep::Action(action));
+
+                    if count > 1 {
+                        filebuffer.begin_group_at(history_index);
+
+                        for _index in 1..count {
+                            unhandled_action = self.textbuffer.handle_action(textbuffer_context, language_manager, filebuffer, action);
+
+                            if unhandled_action.is_some() {
+                                break;
+                            }
+
+                            self.record_step(MacroStep::Action(action));
+                        }
+
+                        filebuffer.end_group();
+                    }
+
+                    self.last_edit.track_action(action, count, filebuffer.changed_text_since(history_index));
+                }
+
+                unhandled_action
+            },
 
             DialogueMode::Open => {
                 let status = self.open_file_dialogue.handle_action(interface_context, language_manager, action);
@@ -226,7 +540,7 @@ impl Interface {
 
                 if status.completed {
                     let file_name = self.open_file_dialogue.get_text();
-                    self.open_buffer(filebuffer_manager, language_manager, file_name);
+                    self.open_buffer(textbuffer_context, filebuffer_manager, language_manager, position_manager, recent_manager, file_name);
                 }
 
                 match status.handled {
@@ -242,9 +556,28 @@ impl Interface {
                     self.dialogue_mode = DialogueMode::None;
                 }
 
+                match self.loaded_buffers_dialogue.take_request() {
+
+                    Some(BufferRequest::Close(file_name)) => {
+                        self.close_buffer(textbuffer_context, filebuffer_manager, language_manager, position_manager, file_name, false);
+                        self.loaded_buffers_dialogue.update_items(filebuffer_manager);
+                    },
+
+                    Some(BufferRequest::Save(file_name)) => {
+                        self.save_buffer(filebuffer_manager, position_manager, recent_manager, file_name);
+                        self.loaded_buffers_dialogue.update_items(filebuffer_manager);
+                    },
+
+                    Some(BufferRequest::Reveal(file_name)) => {
+                        self.dialogue_mode = self.open_file_dialogue.reveal(language_manager, &file_name);
+                    },
+
+                    None => { },
+                }
+
                 if status.completed {
                     let file_name = self.loaded_buffers_dialogue.get_text();
-                    self.open_buffer(filebuffer_manager, language_manager, file_name);
+                    self.open_buffer(textbuffer_context, filebuffer_manager, language_manager, position_manager, recent_manager, file_name);
                 }
 
                 match status.handled {
@@ -366,8 +699,166 @@ impl Interface {
                 }
 
                 if status.completed {
-                    let action = self.action_dialogue.get_value();
-                    return self.handle_action(interface_context, textbuffer_context, filebuffer_manager, language_manager, action, theme_name);
+                    let (action, macro_name) = self.action_dialogue.get_value();
+
+                    if let Some(macro_name) = macro_name {
+                        let filebuffer = filebuffer_manager.get_mut(&self.file_name.serialize());
+                        let steps = match macro_manager.get(&macro_name) {
+                            Some(steps) => steps,
+                            None => return None,
+                        };
+
+                        self.play_macro(textbuffer_context, language_manager, filebuffer, steps, false);
+                        return None;
+                    }
+
+                    return self.handle_action(interface_context, textbuffer_context, filebuffer_manager, language_manager, position_manager, recent_manager, macro_manager, action, theme_name);
+                }
+
+                match status.handled {
+                    true => return None,
+                    false => return Some(action),
+                }
+            },
+
+            DialogueMode::Recent => {
+                let status = self.recent_files_dialogue.handle_action(interface_context, language_manager, action);
+
+                if status.closed {
+                    self.dialogue_mode = DialogueMode::None;
+                }
+
+                if status.completed {
+                    let file_name = self.recent_files_dialogue.get_value();
+                    self.open_buffer(textbuffer_context, filebuffer_manager, language_manager, position_manager, recent_manager, file_name);
+                }
+
+                match status.handled {
+                    true => return None,
+                    false => return Some(action),
+                }
+            },
+
+            DialogueMode::Find => {
+                let status = self.find_file_dialogue.handle_action(interface_context, language_manager, action);
+
+                if status.closed {
+                    self.find_file_dialogue.close();
+                    self.dialogue_mode = DialogueMode::None;
+                }
+
+                if status.completed {
+                    let file_name = self.find_file_dialogue.get_value();
+                    self.open_buffer(textbuffer_context, filebuffer_manager, language_manager, position_manager, recent_manager, file_name);
+                }
+
+                match status.handled {
+                    true => return None,
+                    false => return Some(action),
+                }
+            },
+
+            DialogueMode::CloseBuffer(file_name) => {
+                let status = self.close_buffer_dialogue.handle_action(interface_context, language_manager, action);
+
+                if status.closed {
+                    self.dialogue_mode = DialogueMode::None;
+                }
+
+                if status.completed && self.close_buffer_dialogue.get_value() {
+                    self.close_buffer(textbuffer_context, filebuffer_manager, language_manager, position_manager, file_name, true);
+                }
+
+                match status.handled {
+                    true => return None,
+                    false => return Some(action),
+                }
+            },
+
+            DialogueMode::BindingReport => {
+                let status = self.binding_report_dialogue.handle_action(interface_context, language_manager, action);
+
+                if status.closed {
+                    self.dialogue_mode = DialogueMode::None;
+                }
+
+                if status.completed {
+                    let line = self.binding_report_dialogue.get_value();
+                    self.open_buffer(textbuffer_context, filebuffer_manager, language_manager, position_manager, recent_manager, interface_context.bindings_file.clone());
+
+                    if let Some(line) = line {
+                        let filebuffer = filebuffer_manager.get_mut(&self.file_name.serialize());
+                        self.textbuffer.jump_to_line(textbuffer_context, filebuffer, line - 1);
+                    }
+                }
+
+                match status.handled {
+                    true => return None,
+                    false => return Some(action),
+                }
+            },
+
+            DialogueMode::CheatSheet => {
+                let status = self.cheat_sheet_dialogue.handle_action(interface_context, language_manager, action);
+
+                if status.closed {
+                    self.dialogue_mode = DialogueMode::None;
+                }
+
+                match status.handled {
+                    true => return None,
+                    false => return Some(action),
+                }
+            },
+
+            DialogueMode::Count => {
+                let status = self.count_dialogue.handle_action(language_manager, action);
+
+                if status.closed {
+                    self.dialogue_mode = DialogueMode::None;
+                }
+
+                if status.completed {
+                    self.pending_count = self.count_dialogue.get_value();
+                    self.show_count();
+                }
+
+                match status.handled {
+                    true => return None,
+                    false => return Some(action),
+                }
+            },
+
+            DialogueMode::NameMacro => {
+                let status = self.name_macro_dialogue.handle_action(interface_context, language_manager, action);
+
+                if status.closed {
+                    self.dialogue_mode = DialogueMode::None;
+                }
+
+                if status.completed {
+                    let name = self.name_macro_dialogue.get_text();
+                    confirm_or_error!(self, macro_manager.store(name));
+                }
+
+                match status.handled {
+                    true => return None,
+                    false => return Some(action),
+                }
+            },
+
+            DialogueMode::EditBinding => {
+                let status = self.edit_binding_dialogue.handle_action(interface_context, language_manager, action);
+
+                if status.closed {
+                    self.dialogue_mode = DialogueMode::None;
+                }
+
+                if status.completed {
+                    if let Some((action, binding)) = self.edit_binding_dialogue.get_value() {
+                        confirm_or_error!(self, write_binding(&interface_context.bindings_file, action, &binding));
+                        return Some(Action::Reload);
+                    }
                 }
 
                 match status.handled {
@@ -380,7 +871,7 @@ impl Interface {
         if let Some(action) = unhandled_action {
             match action {
 
-                Action::NewFile => handle_return!(self.new_file(filebuffer_manager, language_manager)),
+                Action::NewFile => handle_return!(self.new_file(filebuffer_manager, language_manager, position_manager)),
 
                 Action::Open => handle_return!(self.dialogue_mode = self.open_file_dialogue.open(language_manager)),
 
@@ -388,7 +879,17 @@ impl Interface {
 
                 Action::Notes => handle_return!(self.dialogue_mode = self.notes_dialogue.open(filebuffer, language_manager)),
 
-                Action::SaveFile => handle_return!(self.save_file(filebuffer_manager)),
+                Action::SaveFile => handle_return!(self.save_file(filebuffer_manager, position_manager, recent_manager)),
+
+                Action::ToggleLineEnding => handle_return!(convert_format(filebuffer, |format| format.line_ending = format.line_ending.toggle())),
+
+                Action::ToggleByteOrderMark => handle_return!(convert_format(filebuffer, |format| format.byte_order_mark = !format.byte_order_mark)),
+
+                Action::ToggleFinalNewline => handle_return!(convert_format(filebuffer, |format| format.final_newline = !format.final_newline)),
+
+                Action::NextEncoding => handle_return!(convert_format(filebuffer, |format| format.encoding = format.encoding.next())),
+
+                Action::CloseBuffer => handle_return!(self.close_buffer(textbuffer_context, filebuffer_manager, language_manager, position_manager, self.file_name.clone(), false)),
 
                 //Action::SaveAllFiles => handle_me_in_core,
 
@@ -398,7 +899,59 @@ impl Interface {
 
                 Action::Replace => handle_return!(self.dialogue_mode = self.find_replace_dialogue.open(language_manager, self.textbuffer.get_selections())),
 
-                Action::Action => handle_return!(self.dialogue_mode = self.action_dialogue.open()),
+                Action::Action => handle_return!(self.dialogue_mode = self.action_dialogue.open(interface_context, macro_manager)),
+
+                Action::NormalMode => handle_return!(self.textbuffer.set_edit_mode(EditMode::Normal)),
+
+                Action::InsertMode => handle_return!(self.textbuffer.set_edit_mode(EditMode::Insert)),
+
+                Action::Abort => match textbuffer_context.modal && self.textbuffer.get_edit_mode() == EditMode::Insert {
+                    true => handle_return!(self.textbuffer.set_edit_mode(EditMode::Normal)),
+                    false => return Some(Action::Abort),
+                },
+
+                Action::RecordMacro => handle_return!(self.toggle_recording(macro_manager)),
+
+                Action::PlayMacro => {
+                    let steps = repeat_steps(&macro_manager.get_last(), self.take_count());
+                    self.play_macro(textbuffer_context, language_manager, filebuffer, steps, false);
+                    return None;
+                },
+
+                Action::PlayMacroSelections => {
+                    let steps = repeat_steps(&macro_manager.get_last(), self.take_count());
+                    self.play_macro(textbuffer_context, language_manager, filebuffer, steps, true);
+                    return None;
+                },
+
+                Action::RepeatLast => {
+                    let steps = self.last_edit.repeat(self.take_count());
+                    self.play_macro(textbuffer_context, language_manager, filebuffer, steps, false);
+                    return None;
+                },
+
+                Action::Count => handle_return!(self.dialogue_mode = self.count_dialogue.open(language_manager)),
+
+                Action::NameMacro => handle_return!(self.dialogue_mode = self.name_macro_dialogue.open(macro_manager, language_manager)),
+
+                Action::Recent => {
+                    let pruned = recent_manager.prune();
+                    self.dialogue_mode = self.recent_files_dialogue.open(recent_manager, language_manager);
+
+                    if let Status::Error(error) = pruned {
+                        self.set_error_state(error);
+                    }
+
+                    return None;
+                },
+
+                Action::FindFile => handle_return!(self.dialogue_mode = self.find_file_dialogue.open(language_manager)),
+
+                Action::BindingReport => handle_return!(self.dialogue_mode = self.binding_report_dialogue.open(interface_context, language_manager)),
+
+                Action::CheatSheet => handle_return!(self.dialogue_mode = self.cheat_sheet_dialogue.open(interface_context, language_manager)),
+
+                Action::EditBinding => handle_return!(self.dialogue_mode = self.edit_binding_dialogue.open(interface_context, language_manager)),
 
                 unhandled => return Some(unhandled),
             }
@@ -407,8 +960,141 @@ impl Interface {
         return None;
     }
 
+    pub fn push_count_digit(&mut self, digit: usize) {
+        let count = self.pending_count.unwrap_or(0);
+        self.pending_count = Some(count.saturating_mul(10).saturating_add(digit));
+        self.show_count();
+    }
+
+    fn show_count(&mut self) {
+        if let Some(count) = self.pending_count {
+            self.textbuffer.set_status_hint(Some(format_shared!("count {}", count)));
+        }
+    }
+
+    fn take_count(&mut self) -> usize {
+        match self.pending_count.take() {
+            Some(count) => {
+                self.textbuffer.set_status_hint(None);
+                return max(count, 1);
+            },
+            None => return 1,
+        }
+    }
+
+    fn record_step(&mut self, step: MacroStep) {
+        if let Some(steps) = &mut self.recording {
+            if let MacroStep::Text(new_text) = &step {
+                if let Some(MacroStep::Text(text)) = steps.last_mut() {
+                    text.push_str(new_text);
+                    return;
+                }
+            }
+
+            steps.push(step);
+        }
+    }
+
+    fn toggle_recording(&mut self, macro_manager: &mut MacroManager) {
+        match self.recording.take() {
+            Some(steps) => macro_manager.set_last(steps),
+            None => self.recording = Some(Vec::new()),
+        }
+
+        self.textbuffer.set_recording(self.recording.is_some());
+    }
+
+    fn play_steps(&mut self, textbuffer_context: &TextbufferContext, language_manager: &mut LanguageManager, filebuffer: &mut Filebuffer, steps: &Vec<MacroStep>) {
+        for step in steps.iter() {
+            match step {
+
+                MacroStep::Action(action) => {
+                    self.textbuffer.handle_action(textbuffer_context, language_manager, filebuffer, *action);
+                },
+
+                MacroStep::Text(text) => {
+                    for index in 0..text.len() {
+                        self.textbuffer.add_character(textbuffer_context, language_manager, filebuffer, text[index]);
+                    }
+                },
+            }
+        }
+    }
+
+    fn play_macro(&mut self, textbuffer_context: &TextbufferContext, language_manager: &mut LanguageManager, filebuffer: &mut Filebuffer, steps: Vec<MacroStep>, per_selection: bool) {
+        if steps.is_empty() {
+            return;
+        }
+
+        filebuffer.begin_group();
+
+        match per_selection {
+
+            true => {
+                let mut selections = self.textbuffer.get_selections();
+                selections.sort_by_key(|selection| min(selection.primary_index, selection.secondary_index));
+                let mut results: Vec<Selection> = Vec::new();
+
+                for selection in selections.into_iter().rev() {
+                    let previous_length = filebuffer.length();
+                    self.textbuffer.set_selections(textbuffer_context, filebuffer, vec![selection]);
+                    self.play_steps(textbuffer_context, language_manager, filebuffer, &steps);
+                    let new_length = filebuffer.length();
+
+                    for result in results.iter_mut() {
+                        match new_length >= previous_length {
+                            true => {
+                                result.primary_index += new_length - previous_length;
+                                result.secondary_index += new_length - previous_length;
+                            },
+                            false => {
+                                result.primary_index = result.primary_index.saturating_sub(previous_length - new_length);
+                                result.secondary_index = result.secondary_index.saturating_sub(previous_length - new_length);
+                            },
+                        }
+                    }
+
+                    let mut new_results = self.textbuffer.get_selections();
+                    new_results.extend(results.into_iter());
+                    results = new_results;
+                }
+
+                self.textbuffer.set_selections(textbuffer_context, filebuffer, results);
+            },
+
+            false => self.play_steps(textbuffer_context, language_manager, filebuffer, &steps),
+        }
+
+        filebuffer.end_group();
+    }
+
+    pub fn is_capturing_binding(&self) -> bool {
+        match self.dialogue_mode {
+            DialogueMode::EditBinding => return self.edit_binding_dialogue.is_capturing(),
+            _other => return false,
+        }
+    }
+
+    pub fn capture_binding(&mut self, interface_context: &InterfaceContext, key_event: KeyEvent) {
+        self.edit_binding_dialogue.capture(interface_context, key_event);
+    }
+
+    pub fn show_binding_report(&mut self, interface_context: &InterfaceContext, language_manager: &mut LanguageManager) {
+        if !interface_context.binding_problems.is_empty() {
+            self.dialogue_mode = self.binding_report_dialogue.open(interface_context, language_manager);
+        }
+    }
+
+    pub fn set_status_hint(&mut self, status_hint: Option<SharedString>) {
+        self.textbuffer.set_status_hint(status_hint);
+    }
+
     pub fn set_error_state(&mut self, error: Error) {
         let message = error.display(&None, &map!());
+        self.set_error_message(message);
+    }
+
+    pub fn set_error_message(&mut self, message: SharedString) {
         self.error_message = Some(message);
         self.dialogue_mode = DialogueMode::None;
     }
@@ -438,9 +1124,43 @@ impl Interface {
 
             DialogueMode::Action => self.action_dialogue.add_character(language_manager, character),
 
+            DialogueMode::Recent => self.recent_files_dialogue.add_character(language_manager, character),
+
+            DialogueMode::Find => self.find_file_dialogue.add_character(language_manager, character),
+
+            DialogueMode::CloseBuffer(..) => self.close_buffer_dialogue.add_character(language_manager, character),
+
+            DialogueMode::BindingReport => self.binding_report_dialogue.add_character(language_manager, character),
+
+            DialogueMode::CheatSheet => self.cheat_sheet_dialogue.add_character(language_manager, character),
+
+            DialogueMode::EditBinding => self.edit_binding_dialogue.add_character(language_manager, character),
+
+            DialogueMode::NameMacro => self.name_macro_dialogue.add_character(language_manager, character),
+
+            DialogueMode::Count => self.count_dialogue.add_character(language_manager, character),
+
             DialogueMode::None => {
+                if textbuffer_context.modal && self.textbuffer.get_edit_mode() == EditMode::Normal {
+                    return;
+                }
+
+                let count = self.take_count();
                 let filebuffer = filebuffer_manager.get_mut(&self.file_name.serialize());
-                self.textbuffer.add_character(textbuffer_context, language_manager, filebuffer, character);
+
+                if count > 1 {
+                    filebuffer.begin_group();
+                }
+
+                for _index in 0..count {
+                    self.textbuffer.add_character(textbuffer_context, language_manager, filebuffer, character);
+                    self.record_step(MacroStep::Text(character.to_string()));
+                    self.last_edit.track_typing(character);
+                }
+
+                if count > 1 {
+                    filebuffer.end_group();
+                }
             },
         }
     }
@@ -470,6 +1190,22 @@ impl Interface {
 
             DialogueMode::Action => self.action_dialogue.render(framebuffer, interface_context, &theme.dialogue_theme),
 
+            DialogueMode::Recent => self.recent_files_dialogue.render(framebuffer, interface_context, &theme.dialogue_theme),
+
+            DialogueMode::Find => self.find_file_dialogue.render(framebuffer, interface_context, &theme.dialogue_theme),
+
+            DialogueMode::CloseBuffer(..) => self.close_buffer_dialogue.render(framebuffer, interface_context, &theme.dialogue_theme),
+
+            DialogueMode::BindingReport => self.binding_report_dialogue.render(framebuffer, interface_context, &theme.dialogue_theme),
+
+            DialogueMode::CheatSheet => self.cheat_sheet_dialogue.render(framebuffer, interface_context, &theme.dialogue_theme),
+
+            DialogueMode::EditBinding => self.edit_binding_dialogue.render(framebuffer, interface_context, &theme.dialogue_theme),
+
+            DialogueMode::NameMacro => self.name_macro_dialogue.render(framebuffer, interface_context, &theme.dialogue_theme),
+
+            DialogueMode::Count => self.count_dialogue.render(framebuffer, interface_context, &theme.dialogue_theme),
+
             DialogueMode::None => { },
         }
     }
@@ -0,0 +1,104 @@
+use seamonkey::*;
+
+use input::Action;
+use managers::MacroStep;
+
+pub fn repeat_steps(steps: &Vec<MacroStep>, count: usize) -> Vec<MacroStep> {
+    return (0..count).flat_map(|_index| steps.iter().cloned()).collect();
+}
+
+pub struct LastEdit {
+    steps: Vec<MacroStep>,
+    typing: bool,
+}
+
+impl LastEdit {
+
+    pub fn new() -> Self {
+        Self {
+            steps: Vec::new(),
+            typing: false,
+        }
+    }
+
+    pub fn track_action(&mut self, action: Action, count: usize, changed_text: bool) {
+        self.typing = false;
+
+        match action {
+            Action::Undo => return,
+            Action::Redo => return,
+            _other => { },
+        }
+
+        if changed_text {
+            self.steps = repeat_steps(&vec![MacroStep::Action(action)], count);
+        }
+    }
+
+    pub fn track_typing(&mut self, character: Character) {
+        match self.typing {
+            true => {
+                if let Some(MacroStep::Text(text)) = self.steps.last_mut() {
+                    text.push_str(&character.to_string());
+                }
+            },
+            false => self.steps = vec![MacroStep::Text(character.to_string())],
+        }
+
+        self.typing = true;
+    }
+
+    pub fn repeat(&mut self, count: usize) -> Vec<MacroStep> {
+        self.typing = false;
+        return repeat_steps(&self.steps, count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn typed_text(steps: &Vec<MacroStep>) -> Vec<String> {
+        return steps.iter().filter_map(|step| match step {
+            MacroStep::Text(text) => Some(text.serialize()),
+            MacroStep::Action(..) => None,
+        }).collect();
+    }
+
+    fn type_text(last_edit: &mut LastEdit, text: &str) {
+        for character in text.chars() {
+            last_edit.track_typing(Character::from_char(character));
+        }
+    }
+
+    #[test]
+    fn typing_is_collected_into_one_step() {
+        let mut last_edit = LastEdit::new();
+        type_text(&mut last_edit, "abc");
+        assert_eq!(typed_text(&last_edit.repeat(2)), vec!["abc", "abc"]);
+    }
+
+    #[test]
+    fn typing_after_repeat_starts_a_new_edit() {
+        let mut last_edit = LastEdit::new();
+        type_text(&mut last_edit, "abc");
+        last_edit.repeat(1);
+        type_text(&mut last_edit, "de");
+        assert_eq!(typed_text(&last_edit.repeat(1)), vec!["de"]);
+    }
+
+    #[test]
+    fn actions_replace_typing() {
+        let mut last_edit = LastEdit::new();
+        type_text(&mut last_edit, "abc");
+        last_edit.track_action(Action::Indent, 3, true);
+        assert_eq!(last_edit.repeat(1).len(), 3);
+
+        last_edit.track_action(Action::Undo, 1, true);
+        assert_eq!(last_edit.repeat(1).len(), 3);
+
+        type_text(&mut last_edit, "x");
+        assert_eq!(typed_text(&last_edit.repeat(1)), vec!["x"]);
+    }
+}
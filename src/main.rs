@@ -35,5 +35,5 @@ fn main() {
         instance.handle_input();
     }
 
-    instance.close();
+    display!(instance.close());
 }
@@ -9,8 +9,197 @@ use std::collections::HashMap;
 use selection::Selection;
 use filebuffer::Filebuffer;
 
+#[derive(Clone, Debug)]
+pub struct LanguageSettings {
+    pub tab_width: Option<usize>,
+    pub indent_operators: Vec<SharedString>,
+    pub dedent_operators: Vec<SharedString>,
+    pub bracket_operators: Vec<(SharedString, SharedString)>,
+    pub pairs: Vec<(Character, Character)>,
+    pub line_comment: Option<SharedString>,
+    pub block_comment: Option<(SharedString, SharedString)>,
+}
+
+impl LanguageSettings {
+
+    pub fn new() -> Self {
+        return Self {
+            tab_width: None,
+            indent_operators: Vec::new(),
+            dedent_operators: Vec::new(),
+            bracket_operators: Vec::new(),
+            pairs: Vec::new(),
+            line_comment: None,
+            block_comment: None,
+        }
+    }
+
+    fn literal_from_data(data: &Data) -> Option<SharedString> {
+        match data {
+            Data::Character(character) => return Some(character.to_string()),
+            Data::String(string) if !string.is_empty() => return Some(string.clone()),
+            _other => return None,
+        }
+    }
+
+    fn load_comments(&mut self, tokenizer_map: &Data) -> Status<()> {
+        let comment_tokenizer = match confirm!(tokenizer_map.index(&keyword!("comment_tokenizer"))) {
+            Some(comment_tokenizer) => comment_tokenizer,
+            None => return success!(()),
+        };
+
+        if let Some(line_comment) = confirm!(comment_tokenizer.index(&keyword!("line_comment"))) {
+            self.line_comment = Self::literal_from_data(&line_comment);
+        } else if let Some(line_comments) = confirm!(comment_tokenizer.index(&keyword!("line_comments"))) {
+            self.line_comment = unpack_list!(&line_comments).iter().filter_map(Self::literal_from_data).next();
+        }
+
+        let block_comment = match confirm!(comment_tokenizer.index(&keyword!("block_comment"))) {
+            Some(block_comment) => Some(block_comment),
+            None => match confirm!(comment_tokenizer.index(&keyword!("block_comments"))) {
+                Some(block_comments) => unpack_list!(&block_comments).iter().next().cloned(),
+                None => None,
+            },
+        };
+
+        if let Some(block_comment) = block_comment {
+            let delimiters = unpack_list!(&block_comment);
+
+            if delimiters.len() != 2 {
+                return error!(string!("block comment expected two items but got {}", delimiters.len()));
+            }
+
+            if let (Some(opening), Some(closing)) = (Self::literal_from_data(&delimiters[0]), Self::literal_from_data(&delimiters[1])) {
+                self.block_comment = Some((opening, closing));
+            }
+        }
+
+        return success!(());
+    }
+
+    fn character_from_data(data: &Data) -> Option<Character> {
+        match data {
+            Data::Character(character) => return Some(*character),
+            Data::String(string) if string.len() == 1 => return Some(string[0]),
+            _other => return None,
+        }
+    }
+
+    fn load_delimiters(tokenizer_map: &Data, tokenizer: &Data) -> Status<Vec<(Character, Character)>> {
+        let mut delimiters = Vec::new();
+
+        if let Some(tokenizer_entry) = confirm!(tokenizer_map.index(tokenizer)) {
+            let mut entries = Vec::new();
+
+            if let Some(delimiter) = confirm!(tokenizer_entry.index(&keyword!("delimiter"))) {
+                entries.push(delimiter);
+            }
+
+            if let Some(delimiter_list) = confirm!(tokenizer_entry.index(&keyword!("delimiters"))) {
+                entries.extend(unpack_list!(&delimiter_list).iter().cloned());
+            }
+
+            for entry in entries.iter() {
+                let delimiter = unpack_list!(entry);
+
+                if delimiter.len() != 2 {
+                    return error!(string!("delimiter expected two items but got {}", delimiter.len()));
+                }
+
+                if let (Some(opening), Some(closing)) = (Self::character_from_data(&delimiter[0]), Self::character_from_data(&delimiter[1])) {
+                    delimiters.push((opening, closing));
+                }
+            }
+        }
+
+        return success!(delimiters);
+    }
+
+    fn operator_character(tokenizer_map: &Data, operator: &SharedString) -> Status<Option<Character>> {
+        if let Some(operator_tokenizer) = confirm!(tokenizer_map.index(&keyword!("operator_tokenizer"))) {
+            if let Some(translate) = confirm!(operator_tokenizer.index(&keyword!("translate"))) {
+                for (key, value) in unpack_map!(&translate).iter() {
+                    if unpack_literal!(value) == *operator {
+                        return success!(Self::character_from_data(key));
+                    }
+                }
+            }
+        }
+
+        return success!(None);
+    }
+
+    fn load_brackets(tokenizer_map: &Data, editor: &Data) -> Status<Vec<(SharedString, SharedString)>> {
+        let mut brackets = Vec::new();
+
+        if let Some(brackets_entry) = confirm!(editor.index(&keyword!("brackets"))) {
+            for bracket in unpack_list!(&brackets_entry).iter() {
+                let operators = unpack_list!(bracket);
+
+                if operators.len() != 2 {
+                    return error!(string!("bracket expected two operators but got {}", operators.len()));
+                }
+
+                let opening = unpack_literal!(&operators[0]);
+                let closing = unpack_literal!(&operators[1]);
+
+                for operator in [&opening, &closing].iter() {
+                    if confirm!(Self::operator_character(tokenizer_map, operator)).is_none() {
+                        return error!(string!("bracket operator {} is not a single character operator", operator.serialize()));
+                    }
+                }
+
+                brackets.push((opening, closing));
+            }
+        }
+
+        return success!(brackets);
+    }
+
+    fn load_operators(editor: &Data, key: &Data) -> Status<Vec<SharedString>> {
+        let mut operators = Vec::new();
+
+        if let Some(operators_entry) = confirm!(editor.index(key)) {
+            for operator in unpack_list!(&operators_entry).iter() {
+                operators.push(unpack_literal!(operator));
+            }
+        }
+
+        return success!(operators);
+    }
+
+    fn load(tokenizer_map: &Data) -> Status<Self> {
+        let mut settings = Self::new();
+
+        if let Some(editor) = confirm!(tokenizer_map.index(&keyword!("editor"))) {
+            match confirm!(editor.index(&keyword!("tab_width"))) {
+                Some(Data::Integer(integer)) if integer > 0 => settings.tab_width = Some(integer as usize),
+                Some(invalid) => return error!(string!("tab_width expected positive integer; found {}", invalid.serialize())),
+                None => { },
+            }
+
+            settings.indent_operators = confirm!(Self::load_operators(&editor, &keyword!("indent")));
+            settings.dedent_operators = confirm!(Self::load_operators(&editor, &keyword!("dedent")));
+            settings.bracket_operators = confirm!(Self::load_brackets(tokenizer_map, &editor));
+        }
+
+        for (opening, closing) in settings.bracket_operators.iter() {
+            let opening = confirm!(Self::operator_character(tokenizer_map, opening)).unwrap();
+            let closing = confirm!(Self::operator_character(tokenizer_map, closing)).unwrap();
+            settings.pairs.push((opening, closing));
+        }
+
+        settings.pairs.extend(confirm!(Self::load_delimiters(tokenizer_map, &keyword!("string_tokenizer"))));
+        settings.pairs.extend(confirm!(Self::load_delimiters(tokenizer_map, &keyword!("character_tokenizer"))));
+        confirm!(settings.load_comments(tokenizer_map));
+
+        return success!(settings);
+    }
+}
+
 pub struct LanguageManager {
     pub tokenizers: HashMap<String, Tokenizer>,
+    pub settings: HashMap<String, LanguageSettings>,
 }
 
 impl LanguageManager {
@@ -18,6 +207,7 @@ impl LanguageManager {
     pub fn new() -> Self {
         return Self {
             tokenizers: HashMap::new(),
+            settings: HashMap::new(),
         }
     }
 
@@ -32,7 +222,9 @@ impl LanguageManager {
             let file_path = format_shared!("/home/.config/poet/languages/{}.data", language);
             let tokenizer_map = confirm!(read_map(&file_path)); // confirm!(read_map(&file_path), Message, "...");
             let tokenizer = confirm!(Tokenizer::new(&tokenizer_map));
+            let settings = confirm!(LanguageSettings::load(&tokenizer_map));
             self.tokenizers.insert(language_string.clone(), tokenizer);
+            self.settings.insert(language_string.clone(), settings);
 
             #[cfg(feature = "debug")]
             timer.stop();
@@ -40,4 +232,9 @@ impl LanguageManager {
 
         return success!(self.tokenizers.get(&language_string).unwrap());
     }
+
+    pub fn get_settings(&mut self, language: &SharedString) -> Status<LanguageSettings> {
+        confirm!(self.get_load(language));
+        return success!(self.settings.get(&language.serialize()).unwrap().clone());
+    }
 }
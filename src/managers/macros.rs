@@ -0,0 +1,149 @@
+use seamonkey::*;
+
+use std::path::Path;
+
+use input::Action;
+
+const MACROS_FILE: &'static str = "/home/.config/poet/macros.data";
+
+#[derive(Clone, Debug)]
+pub enum MacroStep {
+    Action(Action),
+    Text(SharedString),
+}
+
+#[derive(Clone, Debug)]
+pub struct Macro {
+    pub name: SharedString,
+    pub steps: Vec<MacroStep>,
+}
+
+impl Macro {
+
+    pub fn new(name: SharedString, steps: Vec<MacroStep>) -> Self {
+        return Self {
+            name: name,
+            steps: steps,
+        }
+    }
+}
+
+pub struct MacroManager {
+    macros: Vec<Macro>,
+    last: Vec<MacroStep>,
+    load_error: Option<SharedString>,
+}
+
+impl MacroManager {
+
+    pub fn new() -> Self {
+        let mut load_error = None;
+
+        let macros = match Path::new(MACROS_FILE).exists() {
+            true => match Self::load() {
+                Status::Success(macros) => macros,
+                Status::Error(error) => {
+                    load_error = Some(format_shared!("failed to load macros: {}", error.display(&None, &map!())));
+                    Vec::new()
+                },
+            },
+            false => Vec::new(),
+        };
+
+        return Self {
+            macros: macros,
+            last: Vec::new(),
+            load_error: load_error,
+        }
+    }
+
+    fn load_step(step: &Data) -> Status<MacroStep> {
+        match step {
+            Data::Identifier(literal) => return success!(MacroStep::Action(confirm!(Action::from_literal(literal)))),
+            Data::String(text) => return success!(MacroStep::Text(text.clone())),
+            invalid => return error!(string!("macro step expected action or string; found {}", invalid.serialize())),
+        }
+    }
+
+    fn load() -> Status<Vec<Macro>> {
+        let macros_file = SharedString::from(MACROS_FILE);
+        let macros_data = confirm!(read_map(&macros_file));
+        let mut macros = Vec::new();
+
+        if let Some(macros_entry) = confirm!(macros_data.index(&keyword!("macros"))) {
+            for entry in unpack_list!(&macros_entry).iter() {
+                let entry_list = unpack_list!(entry);
+
+                if entry_list.len() != 2 {
+                    return error!(string!("macro expected two items but got {}", entry_list.len()));
+                }
+
+                let name = unpack_literal!(&entry_list[0]);
+                let mut steps = Vec::new();
+
+                for step in unpack_list!(&entry_list[1]).iter() {
+                    steps.push(confirm!(Self::load_step(step)));
+                }
+
+                macros.push(Macro::new(name, steps));
+            }
+        }
+
+        return success!(macros);
+    }
+
+    pub fn save(&self) -> Status<()> {
+        let mut serialized = String::from("#macros [\n");
+
+        for entry in self.macros.iter() {
+            let name = Data::String(entry.name.clone());
+            serialized.push_str(&format!("    [ {} [ ", name.serialize()));
+
+            for step in entry.steps.iter() {
+                match step {
+                    MacroStep::Action(action) => serialized.push_str(&format!("{} ", action.info().literal)),
+                    MacroStep::Text(text) => serialized.push_str(&format!("{} ", Data::String(text.clone()).serialize())),
+                }
+            }
+
+            serialized.push_str("] ]\n");
+        }
+
+        serialized.push_str("]\n");
+        return write_file(&SharedString::from(MACROS_FILE), &SharedString::from(serialized.as_str()));
+    }
+
+    pub fn get_load_error(&self) -> Option<SharedString> {
+        return self.load_error.clone();
+    }
+
+    pub fn set_last(&mut self, steps: Vec<MacroStep>) {
+        self.last = steps;
+    }
+
+    pub fn get_last(&self) -> Vec<MacroStep> {
+        return self.last.clone();
+    }
+
+    pub fn store(&mut self, name: SharedString) -> Status<()> {
+        if self.last.is_empty() {
+            return error!(string!("no macro recorded"));
+        }
+
+        if self.load_error.is_some() {
+            return error!(string!("macros file failed to load and will not be overwritten"));
+        }
+
+        self.macros.retain(|entry| entry.name != name);
+        self.macros.push(Macro::new(name, self.last.clone()));
+        return self.save();
+    }
+
+    pub fn get(&self, name: &SharedString) -> Option<Vec<MacroStep>> {
+        return self.macros.iter().find(|entry| entry.name == *name).map(|entry| entry.steps.clone());
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<Macro> {
+        return self.macros.iter();
+    }
+}
@@ -1,5 +1,11 @@
 mod language;
 mod filebuffer;
+mod position;
+mod recent;
+mod macros;
 
-pub use self::language::LanguageManager;
+pub use self::language::{ LanguageManager, LanguageSettings };
 pub use self::filebuffer::FilebufferManager;
+pub use self::position::{ PositionManager, BufferPosition };
+pub use self::recent::{ RecentManager, RecentEntry };
+pub use self::macros::{ MacroManager, Macro, MacroStep };
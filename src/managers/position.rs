@@ -0,0 +1,130 @@
+use seamonkey::*;
+
+use std::collections::HashMap;
+
+use selection::{ Selection, SelectionMode };
+
+const POSITIONS_FILE: &'static str = "/home/.config/poet/positions.data";
+
+#[derive(Clone, Debug)]
+pub struct BufferPosition {
+    pub selections: Vec<Selection>,
+    pub mode: SelectionMode,
+    pub vertical_scroll: usize,
+    pub horizontal_scroll: usize,
+}
+
+impl BufferPosition {
+
+    fn get_integer(entry: &Data, name: &'static str) -> Status<usize> {
+        match confirm!(entry.index(&identifier!(name))) {
+            Some(Data::Integer(integer)) => return success!(integer as usize),
+            Some(invalid) => return error!(string!("\"{}\" expected integer; found {}", name, invalid.serialize())),
+            None => return success!(0),
+        }
+    }
+
+    fn load(entry: &Data) -> Status<Self> {
+        let mode = match confirm!(entry.index(&identifier!("mode"))) {
+            Some(mode) => confirm!(SelectionMode::from_literal(&unpack_literal!(&mode))),
+            None => SelectionMode::Character,
+        };
+
+        let mut selections = Vec::new();
+
+        if let Some(selections_entry) = confirm!(entry.index(&identifier!("selections"))) {
+            for selection in unpack_list!(&selections_entry).iter() {
+                let mut indices = Vec::new();
+
+                for index in unpack_list!(selection).iter() {
+                    match index {
+                        Data::Integer(integer) => indices.push(*integer as usize),
+                        invalid => return error!(string!("selection expected integer; found {}", invalid.serialize())),
+                    }
+                }
+
+                if indices.len() != 3 {
+                    return error!(string!("selection expected three items but got {}", indices.len()));
+                }
+
+                selections.push(Selection::new(indices[0], indices[1], indices[2]));
+            }
+        }
+
+        if selections.is_empty() {
+            selections.push(Selection::new(0, 0, 0));
+        }
+
+        return success!(Self {
+            selections: selections,
+            mode: mode,
+            vertical_scroll: confirm!(Self::get_integer(entry, "vertical_scroll")),
+            horizontal_scroll: confirm!(Self::get_integer(entry, "horizontal_scroll")),
+        });
+    }
+
+    fn serialize(&self) -> String {
+        let selections: Vec<String> = self.selections.iter().map(|selection| format!("[ {} {} {} ]", selection.primary_index, selection.secondary_index, selection.offset)).collect();
+        let mut serialized = String::new();
+
+        serialized.push_str(&format!("        mode                {}\n", self.mode.name()));
+        serialized.push_str(&format!("        vertical_scroll     {}\n", self.vertical_scroll));
+        serialized.push_str(&format!("        horizontal_scroll   {}\n", self.horizontal_scroll));
+        serialized.push_str(&format!("        selections          [ {} ]\n", selections.join(" ")));
+        return serialized;
+    }
+}
+
+pub struct PositionManager {
+    positions: HashMap<String, BufferPosition>,
+}
+
+impl PositionManager {
+
+    pub fn new() -> Self {
+        let positions = match Self::load() {
+            Status::Success(positions) => positions,
+            Status::Error(_error) => HashMap::new(),
+        };
+
+        return Self {
+            positions: positions,
+        }
+    }
+
+    fn load() -> Status<HashMap<String, BufferPosition>> {
+        let positions_file = SharedString::from(POSITIONS_FILE);
+        let positions_data = confirm!(read_map(&positions_file));
+        let mut positions = HashMap::new();
+
+        if let Some(positions_entry) = confirm!(positions_data.index(&keyword!("positions"))) {
+            for (key, value) in unpack_map!(&positions_entry).iter() {
+                let file_name = unpack_literal!(key).serialize();
+                let position = confirm!(BufferPosition::load(value));
+                positions.insert(file_name, position);
+            }
+        }
+
+        return success!(positions);
+    }
+
+    pub fn save(&self) -> Status<()> {
+        let mut serialized = String::from("#positions {\n");
+
+        for (file_name, position) in self.positions.iter() {
+            let key = Data::String(SharedString::from(file_name.as_str()));
+            serialized.push_str(&format!("\n    {} {{\n{}    }}\n", key.serialize(), position.serialize()));
+        }
+
+        serialized.push_str("}\n");
+        return write_file(&SharedString::from(POSITIONS_FILE), &SharedString::from(serialized.as_str()));
+    }
+
+    pub fn get(&self, file_name: &str) -> Option<&BufferPosition> {
+        return self.positions.get(file_name);
+    }
+
+    pub fn set(&mut self, file_name: String, position: BufferPosition) {
+        self.positions.insert(file_name, position);
+    }
+}
@@ -0,0 +1,113 @@
+use seamonkey::*;
+
+use std::path::Path;
+use std::time::{ SystemTime, UNIX_EPOCH };
+
+const RECENT_FILE: &'static str = "/home/.config/poet/recent.data";
+const RECENT_LIMIT: usize = 50;
+
+#[derive(Clone, Debug)]
+pub struct RecentEntry {
+    pub file_name: String,
+    pub timestamp: u64,
+}
+
+impl RecentEntry {
+
+    pub fn new(file_name: String, timestamp: u64) -> Self {
+        return Self {
+            file_name: file_name,
+            timestamp: timestamp,
+        }
+    }
+
+    pub fn elapsed(&self) -> u64 {
+        let now = RecentManager::now();
+        match now > self.timestamp {
+            true => return now - self.timestamp,
+            false => return 0,
+        }
+    }
+}
+
+pub struct RecentManager {
+    entries: Vec<RecentEntry>,
+}
+
+impl RecentManager {
+
+    pub fn new() -> Self {
+        let entries = match Self::load() {
+            Status::Success(entries) => entries,
+            Status::Error(_error) => Vec::new(),
+        };
+
+        return Self {
+            entries: entries,
+        }
+    }
+
+    fn now() -> u64 {
+        return SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or(0);
+    }
+
+    fn load() -> Status<Vec<RecentEntry>> {
+        let recent_file = SharedString::from(RECENT_FILE);
+        let recent_data = confirm!(read_map(&recent_file));
+        let mut entries = Vec::new();
+
+        if let Some(recent_entry) = confirm!(recent_data.index(&keyword!("recent"))) {
+            for entry in unpack_list!(&recent_entry).iter() {
+                let entry_list = unpack_list!(entry);
+
+                if entry_list.len() != 2 {
+                    return error!(string!("recent file expected two items but got {}", entry_list.len()));
+                }
+
+                let file_name = unpack_literal!(&entry_list[0]).serialize();
+                let timestamp = match &entry_list[1] {
+                    Data::Integer(integer) => *integer as u64,
+                    invalid => return error!(string!("recent file timestamp expected integer; found {}", invalid.serialize())),
+                };
+
+                entries.push(RecentEntry::new(file_name, timestamp));
+            }
+        }
+
+        return success!(entries);
+    }
+
+    pub fn save(&self) -> Status<()> {
+        let mut serialized = String::from("#recent [\n");
+
+        for entry in self.entries.iter() {
+            let file_name = Data::String(SharedString::from(entry.file_name.as_str()));
+            serialized.push_str(&format!("    [ {} {} ]\n", file_name.serialize(), entry.timestamp));
+        }
+
+        serialized.push_str("]\n");
+        return write_file(&SharedString::from(RECENT_FILE), &SharedString::from(serialized.as_str()));
+    }
+
+    pub fn touch(&mut self, file_name: &str) -> Status<()> {
+        self.entries.retain(|entry| entry.file_name != file_name);
+        self.entries.insert(0, RecentEntry::new(file_name.to_string(), Self::now()));
+        self.entries.truncate(RECENT_LIMIT);
+        return self.save();
+    }
+
+    pub fn prune(&mut self) -> Status<()> {
+        let previous_length = self.entries.len();
+        self.entries.retain(|entry| Path::new(&entry.file_name).exists());
+
+        if self.entries.len() != previous_length {
+            return self.save();
+        }
+
+        return success!(());
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<RecentEntry> {
+        return self.entries.iter();
+    }
+}
@@ -1,4 +1,6 @@
-#[derive(Copy, Clone, Debug)]
+use seamonkey::*;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub enum SelectionMode {
     Character,
     Word,
@@ -7,6 +9,15 @@ pub enum SelectionMode {
 
 impl SelectionMode {
 
+    pub fn from_literal(literal: &SharedString) -> Status<Self> {
+        match literal.printable().as_str() {
+            "character" => return success!(SelectionMode::Character),
+            "word" => return success!(SelectionMode::Word),
+            "line" => return success!(SelectionMode::Line),
+            invalid => return error!(string!("invalid selection mode {}", invalid)),
+        }
+    }
+
     pub fn name(&self) -> &'static str {
         match self {
             SelectionMode::Character => "character",
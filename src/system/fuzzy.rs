@@ -0,0 +1,144 @@
+use system::subtract_or_zero;
+
+const MATCH_SCORE: i64 = 16;
+const CONSECUTIVE_BONUS: i64 = 12;
+const BOUNDARY_BONUS: i64 = 10;
+const FILE_NAME_BONUS: i64 = 8;
+const GAP_PENALTY: i64 = 2;
+const LEADING_PENALTY: i64 = 1;
+
+#[derive(Clone, Debug)]
+pub struct FuzzyMatch {
+    pub score: i64,
+    pub positions: Vec<usize>,
+}
+
+fn is_boundary(previous: char) -> bool {
+    match previous {
+        '/' | '\\' | '_' | '-' | '.' | ' ' => return true,
+        _other => return false,
+    }
+}
+
+fn characters_match(pattern: char, text: char, case_sensitive: bool) -> bool {
+    match case_sensitive {
+        true => return pattern == text,
+        false => return pattern == text || pattern.to_lowercase().eq(text.to_lowercase()),
+    }
+}
+
+fn score_positions(text: &[char], positions: &[usize]) -> i64 {
+    let file_name_start = text.iter().rposition(|character| *character == '/').map(|index| index + 1).unwrap_or(0);
+    let mut score = 0;
+
+    for (index, position) in positions.iter().enumerate() {
+        score += MATCH_SCORE;
+
+        if *position == 0 || is_boundary(text[*position - 1]) || (text[*position - 1].is_lowercase() && text[*position].is_uppercase()) {
+            score += BOUNDARY_BONUS;
+        }
+
+        if *position >= file_name_start {
+            score += FILE_NAME_BONUS;
+        }
+
+        match index {
+            0 => score -= LEADING_PENALTY * subtract_or_zero(*position, file_name_start) as i64,
+            _other => match *position == positions[index - 1] + 1 {
+                true => score += CONSECUTIVE_BONUS,
+                false => score -= GAP_PENALTY * (*position - positions[index - 1] - 1).min(8) as i64,
+            },
+        }
+    }
+
+    return score - (text.len() as i64 - file_name_start as i64) / 4;
+}
+
+pub fn fuzzy_match(pattern: &[char], text: &[char]) -> Option<FuzzyMatch> {
+    if pattern.is_empty() {
+        return Some(FuzzyMatch { score: 0, positions: Vec::new() });
+    }
+
+    let case_sensitive = pattern.iter().any(|character| character.is_uppercase());
+    let mut pattern_index = 0;
+    let mut end = 0;
+
+    for (index, character) in text.iter().enumerate() {
+        if characters_match(pattern[pattern_index], *character, case_sensitive) {
+            pattern_index += 1;
+
+            if pattern_index == pattern.len() {
+                end = index;
+                break;
+            }
+        }
+    }
+
+    if pattern_index != pattern.len() {
+        return None;
+    }
+
+    let mut positions = vec![0; pattern.len()];
+    let mut text_index = end + 1;
+
+    for pattern_index in (0..pattern.len()).rev() {
+        loop {
+            text_index -= 1;
+
+            if characters_match(pattern[pattern_index], text[text_index], case_sensitive) {
+                positions[pattern_index] = text_index;
+                break;
+            }
+        }
+    }
+
+    return Some(FuzzyMatch {
+        score: score_positions(text, &positions),
+        positions: positions,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn chars(text: &str) -> Vec<char> {
+        return text.chars().collect();
+    }
+
+    fn score(pattern: &str, text: &str) -> Option<i64> {
+        return fuzzy_match(&chars(pattern), &chars(text)).map(|fuzzy_match| fuzzy_match.score);
+    }
+
+    #[test]
+    fn empty_pattern_matches() {
+        assert_eq!(score("", "anything"), Some(0));
+    }
+
+    #[test]
+    fn missing_characters() {
+        assert_eq!(score("xyz", "src/main.rs"), None);
+        assert_eq!(score("nm", "main"), None);
+    }
+
+    #[test]
+    fn positions() {
+        let fuzzy_match = fuzzy_match(&chars("fb"), &chars("foo_bar")).unwrap();
+        assert_eq!(fuzzy_match.positions, vec![0, 4]);
+    }
+
+    #[test]
+    fn smart_case() {
+        assert!(score("abc", "ABC").is_some());
+        assert!(score("Abc", "abc").is_none());
+        assert!(score("Abc", "Abc").is_some());
+    }
+
+    #[test]
+    fn ranking() {
+        assert!(score("main", "src/main.rs").unwrap() > score("main", "src/domain/info.rs").unwrap());
+        assert!(score("ab", "x/ab.rs").unwrap() > score("ab", "ab/x.rs").unwrap());
+        assert!(score("ab", "a_b.rs").unwrap() > score("ab", "axb.rs").unwrap());
+    }
+}
@@ -18,6 +18,9 @@ pub struct Instance<'i> {
     theme_name: SharedString,
     filebuffer_manager: FilebufferManager,
     language_manager: LanguageManager,
+    position_manager: PositionManager,
+    recent_manager: RecentManager,
+    macro_manager: MacroManager,
     window_counter: usize,
 }
 
@@ -54,6 +57,9 @@ impl<'i> Instance<'i> {
 
         let filebuffer_manager = FilebufferManager::new();
         let language_manager = LanguageManager::new();
+        let position_manager = PositionManager::new();
+        let recent_manager = RecentManager::new();
+        let macro_manager = MacroManager::new();
 
         #[cfg(feature = "debug")]
         manager_timer.stop();
@@ -69,6 +75,9 @@ impl<'i> Instance<'i> {
             theme_name: theme_name,
             filebuffer_manager: filebuffer_manager,
             language_manager: language_manager,
+            position_manager: position_manager,
+            recent_manager: recent_manager,
+            macro_manager: macro_manager,
             window_counter: 0,
         }
     }
@@ -79,6 +88,10 @@ impl<'i> Instance<'i> {
         let timer = Timer::new("new interface");
 
         let mut new_window = confirm!(PoetWindow::interface(&self.interface_context, &mut self.filebuffer_manager, &mut self.language_manager, self.window_counter));
+
+        if let Some(message) = self.macro_manager.get_load_error() {
+            new_window.set_error_message(message);
+        }
         new_window.rerender(&self.interface_context, &self.textbuffer_context, &self.interface_theme, &mut self.filebuffer_manager);
 
         self.window_counter += 1;
@@ -101,12 +114,20 @@ impl<'i> Instance<'i> {
         let mut force_update = false;
 
         'handle: while index < self.windows.len() {
-            for action in self.windows[index].handle_input(&self.interface_context, &self.textbuffer_context, &self.interface_theme, &mut self.filebuffer_manager, &mut self.language_manager, &mut self.theme_name) {
+            for action in self.windows[index].handle_input(&self.interface_context, &self.textbuffer_context, &self.interface_theme, &mut self.filebuffer_manager, &mut self.language_manager, &mut self.position_manager, &mut self.recent_manager, &mut self.macro_manager, &mut self.theme_name) {
                 match action {
 
                     Action::CloseWindow => {
-                        self.windows[index].close();
+                        self.windows[index].close(&mut self.position_manager);
                         self.windows.remove(index);
+
+                        if let Status::Error(error) = self.position_manager.save() {
+                            if let Some(window) = self.windows.first_mut() {
+                                window.set_error_state(error);
+                                force_rerender = true;
+                            }
+                        }
+
                         continue 'handle;
                     },
 
@@ -124,6 +145,20 @@ impl<'i> Instance<'i> {
                         }
                     },
 
+                    Action::IncreaseTabWidth => {
+                        if self.textbuffer_context.increase_tab_width() {
+                            force_update = true;
+                            force_rerender = true;
+                        }
+                    },
+
+                    Action::DecreaseTabWidth => {
+                        if self.textbuffer_context.decrease_tab_width() {
+                            force_update = true;
+                            force_rerender = true;
+                        }
+                    },
+
                     Action::ZoomOut => {
                         if self.interface_context.zoom_out()  {
                             force_update = true;
@@ -191,6 +226,22 @@ impl<'i> Instance<'i> {
                         force_rerender = true;
                     },
 
+                    Action::ToggleSoftWrap => {
+                        self.textbuffer_context.toggle_soft_wrap();
+                        force_update = true;
+                        force_rerender = true;
+                    },
+
+                    Action::ToggleModal => {
+                        self.textbuffer_context.toggle_modal();
+                        force_rerender = true;
+                    },
+
+                    Action::ToggleAutoClose => {
+                        self.textbuffer_context.toggle_auto_close();
+                        force_rerender = true;
+                    },
+
                     Action::Quit => {
                         panic!("implement");
                     },
@@ -218,6 +269,8 @@ impl<'i> Instance<'i> {
                         let interface_context = display!(InterfaceContext::temp());
                         self.interface_context = interface_context;
 
+                        self.windows[index].show_binding_report(&self.interface_context, &mut self.language_manager);
+
                         #[cfg(feature = "debug")]
                         context_timer.stop();
 
@@ -275,6 +328,11 @@ impl<'i> Instance<'i> {
         self.windows.iter_mut().for_each(|window| window.display());
     }
 
-    pub fn close(&self) {
+    pub fn close(&mut self) -> Status<()> {
+        for window in self.windows.iter_mut() {
+            window.close(&mut self.position_manager);
+        }
+
+        return self.position_manager.save();
     }
 }
@@ -1,9 +1,15 @@
 mod instance;
 mod window;
+mod fuzzy;
+mod walker;
+mod unicode;
 
 use self::window::PoetWindow;
 
 pub use self::instance::Instance;
+pub use self::fuzzy::{ FuzzyMatch, fuzzy_match };
+pub use self::walker::walk_project;
+pub use self::unicode::{ code_point, character_width, text_width, is_grapheme_extend, is_joiner };
 
 pub fn subtract_or_zero(left: usize, right: usize) -> usize {
     match left < right {
@@ -0,0 +1,180 @@
+use seamonkey::*;
+
+use std::fmt::{ self, Write };
+
+const ZERO_WIDTH_JOINER: u32 = 0x200D;
+
+const COMBINING_RANGES: &'static [(u32, u32)] = &[
+    (0x0300, 0x036F),
+    (0x0483, 0x0489),
+    (0x0591, 0x05BD),
+    (0x05BF, 0x05BF),
+    (0x05C1, 0x05C2),
+    (0x05C4, 0x05C5),
+    (0x05C7, 0x05C7),
+    (0x0610, 0x061A),
+    (0x064B, 0x065F),
+    (0x0670, 0x0670),
+    (0x06D6, 0x06DC),
+    (0x06DF, 0x06E4),
+    (0x06E7, 0x06E8),
+    (0x06EA, 0x06ED),
+    (0x0900, 0x0903),
+    (0x093A, 0x094F),
+    (0x0951, 0x0957),
+    (0x0962, 0x0963),
+    (0x0E31, 0x0E31),
+    (0x0E34, 0x0E3A),
+    (0x0E47, 0x0E4E),
+    (0x1AB0, 0x1AFF),
+    (0x1DC0, 0x1DFF),
+    (0x200B, 0x200D),
+    (0x20D0, 0x20FF),
+    (0x302A, 0x302F),
+    (0x3099, 0x309A),
+    (0xFE00, 0xFE0F),
+    (0xFE20, 0xFE2F),
+    (0x1F3FB, 0x1F3FF),
+    (0xE0020, 0xE007F),
+    (0xE0100, 0xE01EF),
+];
+
+const WIDE_RANGES: &'static [(u32, u32)] = &[
+    (0x1100, 0x115F),
+    (0x231A, 0x231B),
+    (0x2329, 0x232A),
+    (0x23E9, 0x23EC),
+    (0x23F0, 0x23F0),
+    (0x23F3, 0x23F3),
+    (0x25FD, 0x25FE),
+    (0x2614, 0x2615),
+    (0x2648, 0x2653),
+    (0x267F, 0x267F),
+    (0x2693, 0x2693),
+    (0x26A1, 0x26A1),
+    (0x26AA, 0x26AB),
+    (0x26BD, 0x26BE),
+    (0x26C4, 0x26C5),
+    (0x26CE, 0x26CE),
+    (0x26D4, 0x26D4),
+    (0x26EA, 0x26EA),
+    (0x26F2, 0x26F3),
+    (0x26F5, 0x26F5),
+    (0x26FA, 0x26FA),
+    (0x26FD, 0x26FD),
+    (0x2705, 0x2705),
+    (0x270A, 0x270B),
+    (0x2728, 0x2728),
+    (0x274C, 0x274C),
+    (0x274E, 0x274E),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2795, 0x2797),
+    (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF),
+    (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55),
+    (0x2E80, 0x303E),
+    (0x3041, 0x33FF),
+    (0x3400, 0x4DBF),
+    (0x4E00, 0x9FFF),
+    (0xA000, 0xA4CF),
+    (0xA960, 0xA97F),
+    (0xAC00, 0xD7A3),
+    (0xF900, 0xFAFF),
+    (0xFE10, 0xFE19),
+    (0xFE30, 0xFE6F),
+    (0xFF00, 0xFF60),
+    (0xFFE0, 0xFFE6),
+    (0x1F004, 0x1F004),
+    (0x1F0CF, 0x1F0CF),
+    (0x1F18E, 0x1F18E),
+    (0x1F191, 0x1F19A),
+    (0x1F200, 0x1F251),
+    (0x1F300, 0x1F64F),
+    (0x1F680, 0x1F6FF),
+    (0x1F7E0, 0x1F7EB),
+    (0x1F90C, 0x1F9FF),
+    (0x1FA70, 0x1FAFF),
+    (0x20000, 0x2FFFD),
+    (0x30000, 0x3FFFD),
+];
+
+fn in_ranges(ranges: &[(u32, u32)], code_point: u32) -> bool {
+    let index = ranges.partition_point(|(_start, end)| *end < code_point);
+    return index < ranges.len() && ranges[index].0 <= code_point;
+}
+
+struct FirstCodePoint(Option<u32>);
+
+impl Write for FirstCodePoint {
+
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        if self.0.is_none() {
+            self.0 = text.chars().next().map(|character| character as u32);
+        }
+        return Ok(());
+    }
+}
+
+pub fn code_point(character: Character) -> u32 {
+    let mut first = FirstCodePoint(None);
+    write!(first, "{}", character).ok();
+    return first.0.unwrap_or(0);
+}
+
+pub fn is_grapheme_extend(character: Character) -> bool {
+    return in_ranges(COMBINING_RANGES, code_point(character));
+}
+
+pub fn is_joiner(character: Character) -> bool {
+    return code_point(character) == ZERO_WIDTH_JOINER;
+}
+
+pub fn character_width(character: Character) -> usize {
+    let code_point = code_point(character);
+
+    if in_ranges(COMBINING_RANGES, code_point) {
+        return 0;
+    }
+
+    match in_ranges(WIDE_RANGES, code_point) {
+        true => return 2,
+        false => return 1,
+    }
+}
+
+pub fn text_width(text: &SharedString) -> usize {
+    return (0..text.len()).map(|index| character_width(text[index])).sum();
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn ranges_are_sorted() {
+        assert!(COMBINING_RANGES.windows(2).all(|pair| pair[0].1 < pair[1].0));
+        assert!(WIDE_RANGES.windows(2).all(|pair| pair[0].1 < pair[1].0));
+    }
+
+    #[test]
+    fn range_lookup() {
+        assert!(in_ranges(WIDE_RANGES, 0x1100));
+        assert!(in_ranges(WIDE_RANGES, 0x115F));
+        assert!(!in_ranges(WIDE_RANGES, 0x1160));
+        assert!(!in_ranges(WIDE_RANGES, 0x41));
+        assert!(in_ranges(WIDE_RANGES, 0x3FFFD));
+        assert!(!in_ranges(WIDE_RANGES, 0x3FFFE));
+    }
+
+    #[test]
+    fn widths() {
+        assert_eq!(character_width(Character::from_char('a')), 1);
+        assert_eq!(character_width(Character::from_char('\u{4E2D}')), 2);
+        assert_eq!(character_width(Character::from_char('\u{0301}')), 0);
+        assert_eq!(code_point(Character::from_char('\u{1F600}')), 0x1F600);
+    }
+}
@@ -0,0 +1,286 @@
+use std::fs::{ read_dir, read_to_string };
+use std::path::{ Path, PathBuf };
+use std::sync::mpsc::{ channel, Receiver, Sender };
+use std::thread;
+
+const BATCH_SIZE: usize = 1024;
+const SKIPPED_DIRECTORIES: [&'static str; 2] = [ ".git", "target" ];
+
+struct IgnorePattern {
+    base: String,
+    pattern: Vec<char>,
+    anchored: bool,
+    directory_only: bool,
+    negated: bool,
+}
+
+impl IgnorePattern {
+
+    fn parse(base: &str, line: &str) -> Option<Self> {
+        let mut line = line.trim_end();
+
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        let negated = line.starts_with('!');
+        if negated {
+            line = &line[1..];
+        }
+
+        let directory_only = line.ends_with('/');
+        if directory_only {
+            line = &line[..line.len() - 1];
+        }
+
+        let anchored = line.contains('/');
+        let line = line.trim_start_matches('/');
+
+        if line.is_empty() {
+            return None;
+        }
+
+        return Some(Self {
+            base: base.to_string(),
+            pattern: line.chars().collect(),
+            anchored: anchored,
+            directory_only: directory_only,
+            negated: negated,
+        });
+    }
+
+    fn matches(&self, relative_path: &str, directory: bool) -> bool {
+        if self.directory_only && !directory {
+            return false;
+        }
+
+        let path = match self.base.is_empty() {
+            true => relative_path,
+            false => match relative_path.starts_with(&self.base) && relative_path[self.base.len()..].starts_with('/') {
+                true => &relative_path[self.base.len() + 1..],
+                false => return false,
+            },
+        };
+
+        if self.anchored {
+            let path: Vec<char> = path.chars().collect();
+            return glob_match(&self.pattern, &path);
+        }
+
+        let file_name: Vec<char> = path.rsplit('/').next().unwrap_or(path).chars().collect();
+        return glob_match(&self.pattern, &file_name);
+    }
+}
+
+fn glob_match(pattern: &[char], text: &[char]) -> bool {
+    if pattern.is_empty() {
+        return text.is_empty();
+    }
+
+    if pattern.len() >= 2 && pattern[0] == '*' && pattern[1] == '*' {
+        let remaining = match pattern.get(2) {
+            Some('/') => &pattern[3..],
+            _other => &pattern[2..],
+        };
+        return (0..=text.len()).any(|index| glob_match(remaining, &text[index..]));
+    }
+
+    match pattern[0] {
+
+        '*' => {
+            for index in 0..=text.len() {
+                if glob_match(&pattern[1..], &text[index..]) {
+                    return true;
+                }
+
+                if index < text.len() && text[index] == '/' {
+                    return false;
+                }
+            }
+            return false;
+        },
+
+        '?' => return !text.is_empty() && text[0] != '/' && glob_match(&pattern[1..], &text[1..]),
+
+        character => return !text.is_empty() && text[0] == character && glob_match(&pattern[1..], &text[1..]),
+    }
+}
+
+fn is_ignored(patterns: &Vec<IgnorePattern>, relative_path: &str, directory: bool) -> bool {
+    let mut ignored = false;
+
+    for pattern in patterns.iter() {
+        if pattern.matches(relative_path, directory) {
+            ignored = !pattern.negated;
+        }
+    }
+
+    return ignored;
+}
+
+fn load_ignore_file(patterns: &mut Vec<IgnorePattern>, directory: &Path, base: &str) -> usize {
+    let previous_length = patterns.len();
+
+    if let Ok(content) = read_to_string(directory.join(".gitignore")) {
+        patterns.extend(content.lines().filter_map(|line| IgnorePattern::parse(base, line)));
+    }
+
+    return patterns.len() - previous_length;
+}
+
+fn walk_directory(sender: &Sender<Vec<String>>, batch: &mut Vec<String>, patterns: &mut Vec<IgnorePattern>, directory: &Path, base: &str) -> bool {
+    let added_patterns = load_ignore_file(patterns, directory, base);
+    let mut directories = Vec::new();
+
+    if let Ok(entries) = read_dir(directory) {
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let file_type = match entry.file_type() {
+                Ok(file_type) => file_type,
+                Err(_error) => continue,
+            };
+
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let relative_path = match base.is_empty() {
+                true => file_name.clone(),
+                false => format!("{}/{}", base, file_name),
+            };
+
+            if file_type.is_symlink() && entry.path().is_dir() {
+                continue;
+            }
+
+            if file_type.is_dir() {
+                if !SKIPPED_DIRECTORIES.contains(&file_name.as_str()) && !is_ignored(patterns, &relative_path, true) {
+                    directories.push((entry.path(), relative_path));
+                }
+                continue;
+            }
+
+            if is_ignored(patterns, &relative_path, false) {
+                continue;
+            }
+
+            batch.push(relative_path);
+
+            if batch.len() >= BATCH_SIZE {
+                if sender.send(batch.split_off(0)).is_err() {
+                    return false;
+                }
+            }
+        }
+    }
+
+    directories.sort();
+
+    for (path, relative_path) in directories.iter() {
+        if !walk_directory(sender, batch, patterns, path, relative_path) {
+            return false;
+        }
+    }
+
+    let remaining = patterns.len() - added_patterns;
+    patterns.truncate(remaining);
+    return true;
+}
+
+pub fn walk_project(root: PathBuf) -> Receiver<Vec<String>> {
+    let (sender, receiver) = channel();
+
+    thread::spawn(move || {
+        let mut batch = Vec::new();
+        let mut patterns = Vec::new();
+
+        if walk_directory(&sender, &mut batch, &mut patterns, &root, "") && !batch.is_empty() {
+            sender.send(batch).ok();
+        }
+    });
+
+    return receiver;
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn glob(pattern: &str, text: &str) -> bool {
+        let pattern: Vec<char> = pattern.chars().collect();
+        let text: Vec<char> = text.chars().collect();
+        return glob_match(&pattern, &text);
+    }
+
+    fn patterns(base: &str, lines: &[&str]) -> Vec<IgnorePattern> {
+        return lines.iter().filter_map(|line| IgnorePattern::parse(base, line)).collect();
+    }
+
+    #[test]
+    fn single_star() {
+        assert!(glob("*.rs", "main.rs"));
+        assert!(glob("*", ""));
+        assert!(!glob("*.rs", "main.rc"));
+        assert!(!glob("*.rs", "src/main.rs"));
+        assert!(glob("src/*.rs", "src/main.rs"));
+        assert!(glob("ma?n.rs", "main.rs"));
+        assert!(!glob("src?main.rs", "src/main.rs"));
+    }
+
+    #[test]
+    fn double_star() {
+        assert!(glob("**/test", "test"));
+        assert!(glob("**/test", "a/b/test"));
+        assert!(glob("a/**/b", "a/b"));
+        assert!(glob("a/**/b", "a/x/y/b"));
+        assert!(!glob("a/**/b", "x/a/b"));
+        assert!(glob("logs/**", "logs/a/b.log"));
+    }
+
+    #[test]
+    fn parse_lines() {
+        assert!(IgnorePattern::parse("", "").is_none());
+        assert!(IgnorePattern::parse("", "# comment").is_none());
+        assert!(IgnorePattern::parse("", "/").is_none());
+
+        let pattern = IgnorePattern::parse("", "!/build/").unwrap();
+        assert!(pattern.negated);
+        assert!(pattern.directory_only);
+        assert!(pattern.anchored);
+        assert_eq!(pattern.pattern, vec!['b', 'u', 'i', 'l', 'd']);
+    }
+
+    #[test]
+    fn negation() {
+        let patterns = patterns("", &["*.log", "!keep.log"]);
+        assert!(is_ignored(&patterns, "debug.log", false));
+        assert!(is_ignored(&patterns, "logs/debug.log", false));
+        assert!(!is_ignored(&patterns, "keep.log", false));
+        assert!(!is_ignored(&patterns, "logs/keep.log", false));
+    }
+
+    #[test]
+    fn directory_only() {
+        let patterns = patterns("", &["build/"]);
+        assert!(is_ignored(&patterns, "build", true));
+        assert!(is_ignored(&patterns, "src/build", true));
+        assert!(!is_ignored(&patterns, "build", false));
+    }
+
+    #[test]
+    fn anchored() {
+        let patterns = patterns("", &["/target", "docs/*.html"]);
+        assert!(is_ignored(&patterns, "target", true));
+        assert!(!is_ignored(&patterns, "src/target", true));
+        assert!(is_ignored(&patterns, "docs/index.html", false));
+        assert!(!is_ignored(&patterns, "src/docs/index.html", false));
+    }
+
+    #[test]
+    fn nested_scoping() {
+        let patterns = patterns("src", &["*.tmp", "/generated"]);
+        assert!(is_ignored(&patterns, "src/a.tmp", false));
+        assert!(is_ignored(&patterns, "src/deep/a.tmp", false));
+        assert!(!is_ignored(&patterns, "a.tmp", false));
+        assert!(!is_ignored(&patterns, "srcx/a.tmp", false));
+        assert!(is_ignored(&patterns, "src/generated", true));
+        assert!(!is_ignored(&patterns, "src/deep/generated", true));
+    }
+}
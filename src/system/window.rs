@@ -3,15 +3,21 @@ use seamonkey::*;
 #[cfg(feature = "debug")]
 use debug::*;
 
+use std::time::{ Duration, Instant };
+
 use sfml::{ graphics::*, system::*, window::* };
 
 use input::*;
 use input::Action;
 use themes::InterfaceTheme;
 use interface::{ Interface, InterfaceContext };
-use managers::{ FilebufferManager, LanguageManager };
+use managers::{ FilebufferManager, LanguageManager, PositionManager, RecentManager, MacroManager };
 use elements::TextbufferContext;
 
+const MULTI_CLICK_TIME: Duration = Duration::from_millis(400);
+const MULTI_CLICK_DISTANCE: f32 = 4.0;
+const SEQUENCE_TIMEOUT: Duration = Duration::from_millis(1500);
+
 pub struct PoetWindow<'w> {
     size: Vector2f,
     window: RenderWindow,
@@ -19,6 +25,11 @@ pub struct PoetWindow<'w> {
     framebuffer: RenderTexture,
     interface: Interface,
     focused: bool,
+    last_click: Instant,
+    last_click_position: Vector2f,
+    click_count: usize,
+    pending_keys: Vec<KeyEvent>,
+    pending_since: Instant,
 }
 
 impl<'w> PoetWindow<'w> {
@@ -41,7 +52,9 @@ impl<'w> PoetWindow<'w> {
         let texture_pointer = framebuffer.texture() as *const _;
         surface.set_texture(unsafe { &*texture_pointer }, false);
 
-        let interface = confirm!(Interface::new(filebuffer_manager, language_manager, window_id));
+        let mut interface = confirm!(Interface::new(filebuffer_manager, language_manager, window_id));
+
+        interface.show_binding_report(interface_context, language_manager);
 
         #[cfg(feature = "debug")]
         timer.stop();
@@ -53,18 +66,36 @@ impl<'w> PoetWindow<'w> {
             framebuffer: framebuffer,
             interface: interface,
             focused: true,
+            last_click: Instant::now(),
+            last_click_position: Vector2f::new(0.0, 0.0),
+            click_count: 0,
+            pending_keys: Vec::new(),
+            pending_since: Instant::now(),
         });
     }
 
-    pub fn handle_input(&mut self, interface_context: &InterfaceContext, textbuffer_context: &TextbufferContext, theme: &InterfaceTheme, filebuffer_manager: &mut FilebufferManager, language_manager: &mut LanguageManager, theme_name: &mut SharedString) -> Vec<Action> {
+    pub fn handle_input(&mut self, interface_context: &InterfaceContext, textbuffer_context: &TextbufferContext, theme: &InterfaceTheme, filebuffer_manager: &mut FilebufferManager, language_manager: &mut LanguageManager, position_manager: &mut PositionManager, recent_manager: &mut RecentManager, macro_manager: &mut MacroManager, theme_name: &mut SharedString) -> Vec<Action> {
         let mut action_queue = Vec::new();
         let mut force_rerender = false;
         let mut handled = false;
 
+        if self.interface.validate_buffer(textbuffer_context, filebuffer_manager, language_manager, position_manager) {
+            force_rerender = true;
+        }
+
         if self.interface.history_catch_up(textbuffer_context, filebuffer_manager) {
             force_rerender = true;
         }
 
+        if self.interface.poll_dialogues() {
+            force_rerender = true;
+        }
+
+        if !self.pending_keys.is_empty() && self.pending_since.elapsed() > SEQUENCE_TIMEOUT {
+            self.clear_pending_keys();
+            force_rerender = true;
+        }
+
         'handle: while let Some(event) = self.window.poll_event() {
             match event {
 
@@ -79,8 +110,52 @@ impl<'w> PoetWindow<'w> {
                         let modifiers = Modifiers::from(shift, ctrl, alt, system);
                         let key_event = KeyEvent::new(code, modifiers);
 
-                        for action in interface_context.get_matching_actions(&key_event) {
-                            if let Some(unhandled_action) = self.interface.handle_action(interface_context, textbuffer_context, filebuffer_manager, language_manager, action, theme_name) {
+                        if self.interface.is_capturing_binding() {
+                            self.interface.capture_binding(interface_context, key_event);
+                            self.rerender(interface_context, textbuffer_context, theme, filebuffer_manager);
+                            handled = true;
+                            continue 'handle;
+                        }
+
+                        if self.pending_keys.is_empty() && modifiers == interface_context.count_modifiers {
+                            if let Some(digit) = digit_from_key(code) {
+                                self.interface.push_count_digit(digit);
+                                self.rerender(interface_context, textbuffer_context, theme, filebuffer_manager);
+                                handled = true;
+                                continue 'handle;
+                            }
+                        }
+
+                        if !self.pending_keys.is_empty() && code == Key::ESCAPE {
+                            self.clear_pending_keys();
+                            self.rerender(interface_context, textbuffer_context, theme, filebuffer_manager);
+                            handled = true;
+                            continue 'handle;
+                        }
+
+                        self.pending_keys.push(key_event);
+                        let binding_scopes = self.interface.binding_scopes(textbuffer_context, filebuffer_manager);
+                        let actions = interface_context.get_matching_actions(&binding_scopes, &self.pending_keys);
+
+                        if actions.is_empty() && interface_context.is_sequence_prefix(&binding_scopes, &self.pending_keys) {
+                            self.pending_since = Instant::now();
+                            self.interface.set_status_hint(Some(self.pending_hint()));
+                            self.rerender(interface_context, textbuffer_context, theme, filebuffer_manager);
+                            handled = true;
+                            continue 'handle;
+                        }
+
+                        let sequence = self.pending_keys.len() > 1;
+                        self.clear_pending_keys();
+
+                        if sequence && actions.is_empty() {
+                            self.rerender(interface_context, textbuffer_context, theme, filebuffer_manager);
+                            handled = true;
+                            continue 'handle;
+                        }
+
+                        for action in actions {
+                            if let Some(unhandled_action) = self.interface.handle_action(interface_context, textbuffer_context, filebuffer_manager, language_manager, position_manager, recent_manager, macro_manager, action, theme_name) {
                                 if unhandled_action.is_global() {
                                     action_queue.push(unhandled_action);
                                     handled = true;
@@ -123,8 +198,8 @@ impl<'w> PoetWindow<'w> {
                     let character = match unicode as usize {
                         13 => Character::from_char('\n'),
                         0..=31 => continue 'handle,
-                        32..=126 => Character::from_char(unicode),
-                        _other => continue 'handle,
+                        127..=159 => continue 'handle,
+                        _other => Character::from_char(unicode),
                     };
 
                     #[cfg(feature = "debug")]
@@ -169,22 +244,66 @@ impl<'w> PoetWindow<'w> {
                     let timer = Timer::new("lost focus");
 
                     self.focused = false;
+                    self.interface.handle_mouse(interface_context, textbuffer_context, theme, filebuffer_manager, language_manager, position_manager, recent_manager, macro_manager, MouseEvent::Released, theme_name);
                     force_rerender = true;
 
                     #[cfg(feature = "debug")]
                     timer.stop();
                 },
 
-                Event::MouseWheelScrolled { delta, .. } => {
+                Event::MouseButtonPressed { button: mouse::Button::LEFT, x, y } => {
+
+                    #[cfg(feature = "debug")]
+                    let timer = Timer::new("mouse press");
+
+                    let position = Vector2f::new(x as f32, y as f32);
+                    let distance = position - self.last_click_position;
+                    let add_selection = Key::LCONTROL.is_pressed() || Key::RCONTROL.is_pressed();
+
+                    match self.last_click.elapsed() < MULTI_CLICK_TIME && distance.x.abs() < MULTI_CLICK_DISTANCE && distance.y.abs() < MULTI_CLICK_DISTANCE {
+                        true => self.click_count += 1,
+                        false => self.click_count = 1,
+                    }
+
+                    self.last_click = Instant::now();
+                    self.last_click_position = position;
+
+                    if self.interface.handle_mouse(interface_context, textbuffer_context, theme, filebuffer_manager, language_manager, position_manager, recent_manager, macro_manager, MouseEvent::Pressed(position, self.click_count, add_selection), theme_name) {
+                        force_rerender = true;
+                    }
+
+                    #[cfg(feature = "debug")]
+                    timer.stop();
+                },
+
+                Event::MouseMoved { x, y } => {
+                    let position = Vector2f::new(x as f32, y as f32);
+
+                    if self.interface.handle_mouse(interface_context, textbuffer_context, theme, filebuffer_manager, language_manager, position_manager, recent_manager, macro_manager, MouseEvent::Moved(position), theme_name) {
+                        force_rerender = true;
+                    }
+                },
+
+                Event::MouseButtonReleased { button: mouse::Button::LEFT, .. } => {
+                    if self.interface.handle_mouse(interface_context, textbuffer_context, theme, filebuffer_manager, language_manager, position_manager, recent_manager, macro_manager, MouseEvent::Released, theme_name) {
+                        force_rerender = true;
+                    }
+                },
+
+                Event::MouseWheelScrolled { wheel, delta, .. } => {
 
                     #[cfg(feature = "debug")]
                     let timer = Timer::new("mouse wheel scroll");
 
-                    match delta > 0.0 {
-                        true => self.interface.scroll_up(textbuffer_context),
-                        false => self.interface.scroll_down(filebuffer_manager, textbuffer_context),
+                    let horizontal = wheel == mouse::Wheel::HorizontalWheel || Key::LSHIFT.is_pressed() || Key::RSHIFT.is_pressed();
+                    let mouse_event = match horizontal {
+                        true => MouseEvent::ScrolledHorizontally(delta),
+                        false => MouseEvent::Scrolled(delta),
+                    };
+
+                    if self.interface.handle_mouse(interface_context, textbuffer_context, theme, filebuffer_manager, language_manager, position_manager, recent_manager, macro_manager, mouse_event, theme_name) {
+                        force_rerender = true;
                     }
-                    force_rerender = true;
 
                     #[cfg(feature = "debug")]
                     timer.stop();
@@ -201,6 +320,23 @@ impl<'w> PoetWindow<'w> {
         return action_queue;
     }
 
+    fn pending_hint(&self) -> SharedString {
+        let mut hint = SharedString::new();
+
+        for key_event in self.pending_keys.iter() {
+            hint.push_str(&key_event.display());
+            hint.push_str(&SharedString::from(", "));
+        }
+
+        hint.push_str(&SharedString::from("..."));
+        return hint;
+    }
+
+    fn clear_pending_keys(&mut self) {
+        self.pending_keys.clear();
+        self.interface.set_status_hint(None);
+    }
+
     pub fn reallocate(&mut self, interface_context: &InterfaceContext) {
 
         #[cfg(feature = "debug")]
@@ -251,15 +387,24 @@ impl<'w> PoetWindow<'w> {
         self.window.display();
     }
 
+    pub fn show_binding_report(&mut self, interface_context: &InterfaceContext, language_manager: &mut LanguageManager) {
+        self.interface.show_binding_report(interface_context, language_manager);
+    }
+
     pub fn set_error_state(&mut self, error: Error) {
         self.interface.set_error_state(error);
     }
 
-    pub fn close(&mut self) {
+    pub fn set_error_message(&mut self, message: SharedString) {
+        self.interface.set_error_message(message);
+    }
+
+    pub fn close(&mut self, position_manager: &mut PositionManager) {
 
         #[cfg(feature = "debug")]
         let timer = Timer::new("close window");
 
+        self.interface.store_position(position_manager);
         self.window.close();
 
         #[cfg(feature = "debug")]
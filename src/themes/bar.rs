@@ -2,6 +2,8 @@ use themes::*;
 
 pub struct StatusBarTheme {
     pub textfield_theme: TextfieldTheme,
+    pub normal_mode_theme: TextfieldTheme,
+    pub insert_mode_theme: TextfieldTheme,
     pub height: f32,
 }
 
@@ -10,6 +12,8 @@ impl StatusBarTheme {
     pub fn load(theme: Option<Data>) -> Self {
         return Self {
             textfield_theme: TextfieldTheme::load(get_subtheme(&theme, "textfield")),
+            normal_mode_theme: TextfieldTheme::load(get_subtheme(&theme, "normal_mode")),
+            insert_mode_theme: TextfieldTheme::load(get_subtheme(&theme, "insert_mode")),
             height: get_float(&theme, "height", 1.5),
         }
     }
@@ -7,6 +7,7 @@ pub struct DialogueTheme {
     pub unfocused_textbox_theme: TextboxTheme,
     pub focused_item_theme: ItemTheme,
     pub unfocused_item_theme: ItemTheme,
+    pub hovered_item_theme: ItemTheme,
     pub display_height: f32,
     pub height: f32,
 }
@@ -19,6 +20,7 @@ impl DialogueTheme {
             unfocused_textbox_theme: TextboxTheme::load(get_subtheme(&theme, "unfocused_textbox")),
             focused_item_theme: ItemTheme::load(get_subtheme(&theme, "focused_element")),
             unfocused_item_theme: ItemTheme::load(get_subtheme(&theme, "unfocused_element")),
+            hovered_item_theme: ItemTheme::load(get_subtheme(&theme, "hovered_element")),
             display_height: get_float(&theme, "display_height", 0.85),
             height: get_float(&theme, "height", 1.5),
         }
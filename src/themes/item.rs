@@ -5,6 +5,7 @@ use themes::*;
 pub struct ItemTheme {
     pub default_theme: TextfieldTheme,
     pub special_theme: TextfieldTheme,
+    pub matched_theme: TextTheme,
     pub padding: f32,
 }
 
@@ -14,6 +15,7 @@ impl ItemTheme {
         return Self {
             default_theme: TextfieldTheme::load(get_subtheme(&theme, "default")),
             special_theme: TextfieldTheme::load(get_subtheme(&theme, "special")),
+            matched_theme: TextTheme::load(get_subtheme(&theme, "matched")),
             padding: get_float(&theme, "padding", 0.0),
         }
     }
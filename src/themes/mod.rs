@@ -42,6 +42,17 @@ fn get_subtheme(theme: &Option<Data>, name: &'static str) -> Option<Data> {
     return None
 }
 
+fn get_subtheme_list(theme: &Option<Data>, name: &'static str) -> Vec<Data> {
+    if let Some(entry) = get_subtheme(theme, name) {
+        if entry.is_list() {
+            return extract_list!(entry).iter().cloned().collect();
+        }
+
+        println!("\"{}\" expected list; found {}", name, entry.serialize());
+    }
+    return Vec::new();
+}
+
 fn alignment_from_string(string: SharedString, name: &'static str, default_value: Alignment) -> Alignment {
     match string.serialize().as_str() {
         "left" => return Alignment::Left,
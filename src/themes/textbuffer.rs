@@ -9,6 +9,8 @@ pub struct TextbufferTheme {
     pub selection_theme: SelectionTheme,
     pub new_selection_theme: SelectionTheme,
     pub selection_line_theme: FieldTheme,
+    pub clip_theme: FieldTheme,
+    pub matching_bracket_theme: FieldTheme,
     pub status_bar_theme: StatusBarTheme,
     pub line_number_theme: TextfieldTheme,
     pub highlighted_line_number_theme: TextfieldTheme,
@@ -24,10 +26,12 @@ pub struct TextbufferTheme {
     pub float_theme: TextTheme,
     pub invalid_theme: TextTheme,
     pub ignored_theme: TextTheme,
+    pub bracket_themes: Vec<TextTheme>,
     pub offset: Vector2f,
     pub line_number_width: f32,
     pub line_number_offset: f32,
     pub line_number_gap: f32,
+    pub clip_width: f32,
 }
 
 impl TextbufferTheme {
@@ -38,6 +42,8 @@ impl TextbufferTheme {
             selection_theme: SelectionTheme::load(get_subtheme(&theme, "selection")),
             new_selection_theme: SelectionTheme::load(get_subtheme(&theme, "new_selection")),
             selection_line_theme: FieldTheme::load(get_subtheme(&theme, "selection_line")),
+            clip_theme: FieldTheme::load(get_subtheme(&theme, "clip")),
+            matching_bracket_theme: FieldTheme::load(get_subtheme(&theme, "matching_bracket")),
             status_bar_theme: StatusBarTheme::load(get_subtheme(&theme, "status_bar")),
             line_number_theme: TextfieldTheme::load(get_subtheme(&theme, "line_number")),
             highlighted_line_number_theme: TextfieldTheme::load(get_subtheme(&theme, "highlighted_line_number")),
@@ -53,10 +59,12 @@ impl TextbufferTheme {
             float_theme: TextTheme::load(get_subtheme(&theme, "float")),
             invalid_theme: TextTheme::load(get_subtheme(&theme, "invalid")),
             ignored_theme: TextTheme::load(get_subtheme(&theme, "ignored")),
+            bracket_themes: get_subtheme_list(&theme, "brackets").into_iter().map(|entry| TextTheme::load(Some(entry))).collect(),
             offset: get_offset(&theme, "offset", Vector2f::new(1.0, 0.0)),
             line_number_width: get_float(&theme, "line_number_width", 4.0),
             line_number_offset: get_float(&theme, "line_number_offset", 1.0),
             line_number_gap: get_float(&theme, "line_number_gap", 0.0),
+            clip_width: get_float(&theme, "clip_width", 0.25),
         }
     }
 }